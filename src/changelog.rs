@@ -0,0 +1,702 @@
+use chrono::Local;
+use crossterm::style::Stylize;
+use std::collections::HashMap;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::git::Git;
+use crate::tools::Tools;
+
+const CHANGELOG: &str = "CHANGELOG.md";
+
+/// Section of `curator.json` mapping commit types to changelog sections
+const MAPPING_SECTION: &str = "changelog";
+
+/// Keep a Changelog sections in the order they are written
+pub const SECTIONS: &[&str] = &[
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
+/// Where conventional commit types go unless `curator.json` says otherwise
+const DEFAULT_MAPPING: &[(&str, &str)] = &[
+    ("feat", "Added"),
+    ("fix", "Fixed"),
+    ("perf", "Changed"),
+    ("revert", "Removed"),
+    ("deprecate", "Deprecated"),
+    ("security", "Security"),
+    ("breaking", "Changed"),
+];
+
+const HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+";
+
+/// A version heading and everything below it up to the next one
+#[derive(Clone, Debug)]
+pub struct Release {
+    /// `Unreleased` or a version without the `v` prefix
+    pub version: String,
+    pub date: Option<String>,
+    /// Sections such as `Added` with their entries, in file order
+    pub sections: Vec<(String, Vec<String>)>,
+}
+
+/// A CHANGELOG.md in Keep a Changelog format
+#[derive(Clone, Debug)]
+pub struct Changelog {
+    /// Text before the first version heading
+    pub header: String,
+    pub releases: Vec<Release>,
+}
+
+/// A commit message following the Conventional Commits format
+#[derive(Clone, Debug)]
+pub struct ConventionalCommit {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+pub struct ChangelogManager;
+
+impl Release {
+    fn new(version: &str, date: Option<String>) -> Self {
+        Release {
+            version: version.to_string(),
+            date,
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn is_unreleased(&self) -> bool {
+        self.version.eq_ignore_ascii_case("unreleased")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.iter().all(|(_, entries)| entries.is_empty())
+    }
+
+    /// Adds an entry to a section unless it is already there, keeping sections in Keep a Changelog order
+    pub fn add(&mut self, section: &str, entry: String) {
+        if let Some((_, entries)) = self.sections.iter_mut().find(|(name, _)| name == section) {
+            if !entries.contains(&entry) {
+                entries.push(entry);
+            }
+            return;
+        }
+        let rank = |name: &str| {
+            SECTIONS
+                .iter()
+                .position(|s| *s == name)
+                .unwrap_or(SECTIONS.len())
+        };
+        let position = self
+            .sections
+            .iter()
+            .position(|(name, _)| rank(name) > rank(section))
+            .unwrap_or(self.sections.len());
+        self.sections
+            .insert(position, (section.to_string(), vec![entry]));
+    }
+}
+
+impl Changelog {
+    /// Parses CHANGELOG.md, dropping the comparison links which are rebuilt on render
+    pub fn parse(text: &str) -> Self {
+        let mut header = String::new();
+        let mut releases: Vec<Release> = Vec::new();
+        for line in text.lines() {
+            if let Some(heading) = line.strip_prefix("## ") {
+                let (version, date) = match heading.split_once(" - ") {
+                    Some((version, date)) => (version, Some(date.trim().to_string())),
+                    None => (heading, None),
+                };
+                let version = version.trim().trim_matches(['[', ']']);
+                releases.push(Release::new(version.trim_start_matches('v'), date));
+                continue;
+            }
+            let Some(release) = releases.last_mut() else {
+                header.push_str(line);
+                header.push('\n');
+                continue;
+            };
+            if Self::is_version_link(line) {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix("### ") {
+                release
+                    .sections
+                    .push((section.trim().to_string(), Vec::new()));
+            } else if line.trim().is_empty() {
+                continue;
+            } else {
+                if release.sections.is_empty() {
+                    release.sections.push((String::new(), Vec::new()));
+                }
+                let entries = &mut release.sections.last_mut().unwrap().1;
+                match entries.last_mut() {
+                    Some(entry) if !line.starts_with("- ") && !line.starts_with("* ") => {
+                        entry.push('\n');
+                        entry.push_str(line);
+                    }
+                    _ => entries.push(line.to_string()),
+                }
+            }
+        }
+        let header = header.trim_end().to_string();
+        Changelog {
+            header: if header.is_empty() {
+                HEADER.trim_end().to_string()
+            } else {
+                header
+            },
+            releases,
+        }
+    }
+
+    fn is_version_link(line: &str) -> bool {
+        line.starts_with('[')
+            && line.split_once("]: ").is_some_and(|(label, _)| {
+                let label = &label[1..];
+                label.eq_ignore_ascii_case("unreleased")
+                    || Git::parse_semver(label).is_some()
+                    || label.starts_with(|c: char| c.is_ascii_digit())
+            })
+    }
+
+    /// Returns the `Unreleased` release, adding it at the top if missing
+    pub fn unreleased(&mut self) -> &mut Release {
+        if !self.releases.first().is_some_and(Release::is_unreleased) {
+            self.releases.insert(0, Release::new("Unreleased", None));
+        }
+        &mut self.releases[0]
+    }
+
+    /// Merges newly collected entries and releases into the changelog
+    ///
+    /// Entries already listed under any release, whether in the file or among `generated`,
+    /// are dropped from `Unreleased` so released changes are not listed twice.
+    pub fn merge(&mut self, unreleased: Release, generated: Vec<Release>) {
+        let released: Vec<String> = self
+            .releases
+            .iter()
+            .chain(generated.iter())
+            .filter(|release| !release.is_unreleased())
+            .flat_map(|release| release.sections.iter())
+            .flat_map(|(_, entries)| entries.iter().cloned())
+            .collect();
+        for (_, entries) in &mut self.unreleased().sections {
+            entries.retain(|entry| !released.contains(entry));
+        }
+        for (section, entries) in unreleased.sections {
+            for entry in entries {
+                if !released.contains(&entry) {
+                    self.unreleased().add(&section, entry);
+                }
+            }
+        }
+        for release in generated {
+            let position = self
+                .releases
+                .iter()
+                .position(|existing| {
+                    !existing.is_unreleased()
+                        && Tools::compare_versions(&existing.version, &release.version).is_lt()
+                })
+                .unwrap_or(self.releases.len());
+            self.releases.insert(position, release);
+        }
+    }
+
+    /// Renders the changelog with comparison links below the releases
+    pub fn render(&self, tag_prefix: &str) -> String {
+        let mut text = format!("{}\n", self.header);
+        for release in &self.releases {
+            text.push_str(&format!("\n## [{}]", release.version));
+            if let Some(date) = &release.date {
+                text.push_str(&format!(" - {date}"));
+            }
+            text.push('\n');
+            for (section, entries) in &release.sections {
+                if entries.is_empty() {
+                    continue;
+                }
+                if !section.is_empty() {
+                    text.push_str(&format!("\n### {section}\n"));
+                }
+                text.push('\n');
+                for entry in entries {
+                    text.push_str(entry);
+                    text.push('\n');
+                }
+            }
+        }
+
+        let links = self.links(tag_prefix);
+        if !links.is_empty() {
+            text.push('\n');
+            text.push_str(&links.join("\n"));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Builds `[version]: url` comparison links from the `origin` remote
+    fn links(&self, tag_prefix: &str) -> Vec<String> {
+        let Some(web) = Git::web_url() else {
+            return Vec::new();
+        };
+        let gitlab = web.contains("gitlab");
+        let compare = |from: &str, to: &str| {
+            if gitlab {
+                format!("{web}/-/compare/{from}...{to}")
+            } else {
+                format!("{web}/compare/{from}...{to}")
+            }
+        };
+        let tag = |version: &str| format!("{tag_prefix}{version}");
+        self.releases
+            .iter()
+            .enumerate()
+            .map(|(i, release)| {
+                let target = if release.is_unreleased() {
+                    "HEAD".to_string()
+                } else {
+                    tag(&release.version)
+                };
+                let url = match self.releases.get(i + 1) {
+                    Some(previous) => compare(&tag(&previous.version), &target),
+                    None if release.is_unreleased() => match gitlab {
+                        true => format!("{web}/-/commits/HEAD"),
+                        false => format!("{web}/commits/HEAD"),
+                    },
+                    None if gitlab => format!("{web}/-/tags/{target}"),
+                    None => format!("{web}/releases/tag/{target}"),
+                };
+                format!("[{}]: {url}", release.version)
+            })
+            .collect()
+    }
+}
+
+impl ConventionalCommit {
+    /// Parses `type(scope)!: description`, reading `BREAKING CHANGE:` footers from the body
+    pub fn parse(subject: &str, body: &str) -> Option<Self> {
+        let (prefix, description) = subject.split_once(": ")?;
+        let breaking = prefix.ends_with('!')
+            || body.lines().any(|line| {
+                line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+            });
+        let prefix = prefix.trim_end_matches('!');
+        let (kind, scope) = match prefix.split_once('(') {
+            Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?.to_string())),
+            None => (prefix, None),
+        };
+        if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return None;
+        }
+        Some(ConventionalCommit {
+            kind: kind.to_lowercase(),
+            scope: scope.filter(|scope| !scope.is_empty()),
+            breaking,
+            description: description.trim().to_string(),
+        })
+    }
+}
+
+impl ChangelogManager {
+    /// Writes or updates CHANGELOG.md from the conventional commits between release tags
+    ///
+    /// Versions already in the file are kept as written unless `rewrite`; new commits
+    /// are merged into `Unreleased`.
+    pub fn generate_changelog(rewrite: bool) {
+        ConfigManager::load_config();
+        if !Git::is_repository() {
+            eprintln!("{} Not a git repository.", "[ERROR]".red());
+            process::exit(1);
+        }
+        let mapping = Self::mapping();
+        let existing = std::fs::read_to_string(CHANGELOG).ok();
+        let mut changelog = match &existing {
+            Some(text) if !rewrite => Changelog::parse(text),
+            Some(text) => Changelog {
+                header: Changelog::parse(text).header,
+                releases: Vec::new(),
+            },
+            None => Changelog::parse(""),
+        };
+
+        let tags = Git::semver_tags();
+        let mut generated = Vec::new();
+        let mut unreleased = Release::new("Unreleased", None);
+        let newest = tags.first().map(|(tag, _)| tag.as_str());
+        let range = match newest {
+            Some(tag) => format!("{tag}..HEAD"),
+            None => "HEAD".to_string(),
+        };
+        Self::collect(&range, &mapping, &mut unreleased);
+        for (i, (tag, _)) in tags.iter().enumerate() {
+            let version = tag.trim_start_matches('v');
+            if changelog
+                .releases
+                .iter()
+                .any(|release| release.version == version)
+            {
+                continue;
+            }
+            let range = match tags.get(i + 1) {
+                Some((previous, _)) => format!("{previous}..{tag}"),
+                None => tag.clone(),
+            };
+            let date = Git::run(&["log", "-1", "--format=%as", tag]);
+            let mut release = Release::new(version, date);
+            Self::collect(&range, &mapping, &mut release);
+            generated.push(release);
+        }
+
+        let added = generated.len();
+        changelog.merge(unreleased, generated);
+
+        match Tools::write_file(CHANGELOG, &changelog.render(&Self::tag_prefix())) {
+            Ok(()) => println!(
+                "{} {} {} ({} new release{})",
+                "[SUCCESS]".green(),
+                if existing.is_some() {
+                    "Updated"
+                } else {
+                    "Created"
+                },
+                CHANGELOG,
+                added,
+                if added == 1 { "" } else { "s" }
+            ),
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Adds an entry under `Unreleased`
+    ///
+    /// `kind` is a Keep a Changelog section such as `fixed` or a commit type such as `fix`.
+    pub fn add_entry(kind: String, message: String) {
+        ConfigManager::load_config();
+        let section = SECTIONS
+            .iter()
+            .find(|section| section.eq_ignore_ascii_case(&kind))
+            .map(|section| section.to_string())
+            .or_else(|| {
+                Self::mapping()
+                    .get(&kind.to_lowercase())
+                    .filter(|section| !section.is_empty())
+                    .cloned()
+            });
+        let Some(section) = section else {
+            eprintln!(
+                "{} Unknown change type '{}'. Use one of: {}",
+                "[ERROR]".red(),
+                kind,
+                SECTIONS.join(", ").to_lowercase()
+            );
+            process::exit(1);
+        };
+        let message = message.trim();
+        let message = message.strip_prefix("- ").unwrap_or(message).trim();
+        if message.is_empty() {
+            eprintln!("{} The message is empty.", "[ERROR]".red());
+            process::exit(1);
+        }
+
+        let mut changelog = Self::read();
+        changelog.unreleased().add(&section, format!("- {message}"));
+        Self::write(&changelog);
+        println!(
+            "{} Added to {} under Unreleased",
+            "[SUCCESS]".green(),
+            section
+        );
+    }
+
+    /// Moves the `Unreleased` entries under a version heading dated today
+    pub fn release(version: String) {
+        ConfigManager::load_config();
+        let version = version.trim().trim_start_matches('v').to_string();
+        if Git::parse_semver(&version).is_none() {
+            eprintln!(
+                "{} '{}' is not a version like 1.2.3.",
+                "[ERROR]".red(),
+                version
+            );
+            process::exit(1);
+        }
+        let mut changelog = Self::read();
+        if changelog
+            .releases
+            .iter()
+            .any(|release| release.version == version)
+        {
+            eprintln!(
+                "{} {} is already in {}.",
+                "[ERROR]".red(),
+                version,
+                CHANGELOG
+            );
+            process::exit(1);
+        }
+        if changelog.unreleased().is_empty() {
+            eprintln!("{} There are no unreleased changes.", "[ERROR]".red());
+            println!(
+                "{} Run `{}` or `{}` first",
+                "[FIX]".green(),
+                "cu changelog add <type> <message>".grey(),
+                "cu changelog generate".grey()
+            );
+            process::exit(1);
+        }
+
+        let sections = std::mem::take(&mut changelog.unreleased().sections);
+        let mut release = Release::new(&version, Some(Self::today()));
+        release.sections = sections;
+        changelog.releases.insert(1, release);
+        Self::write(&changelog);
+        println!(
+            "{} Released {} in {}",
+            "[SUCCESS]".green(),
+            version,
+            CHANGELOG
+        );
+    }
+
+    /// Reads CHANGELOG.md, starting a new one when there is none
+    fn read() -> Changelog {
+        match std::fs::read_to_string(CHANGELOG) {
+            Ok(text) => Changelog::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Changelog::parse(""),
+            Err(e) => {
+                eprintln!("{} Failed to read {}: {}", "[ERROR]".red(), CHANGELOG, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    fn write(changelog: &Changelog) {
+        if let Err(e) = Tools::write_file(CHANGELOG, &changelog.render(&Self::tag_prefix())) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+
+    /// Adds the conventional commits in a revision range to a release
+    fn collect(range: &str, mapping: &HashMap<String, String>, release: &mut Release) {
+        let log = Git::run(&["log", "--no-merges", "--format=%h%x1f%s%x1f%b%x1e", range])
+            .unwrap_or_default();
+        let web = Git::web_url();
+        for record in log.split('\u{1e}') {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\u{1f}');
+            let (Some(hash), Some(subject)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let Some(commit) = ConventionalCommit::parse(subject, fields.next().unwrap_or(""))
+            else {
+                continue;
+            };
+            let key = if commit.breaking {
+                "breaking"
+            } else {
+                commit.kind.as_str()
+            };
+            let Some(section) = mapping
+                .get(key)
+                .or_else(|| mapping.get(&commit.kind))
+                .filter(|section| !section.is_empty())
+            else {
+                continue;
+            };
+            let mut entry = String::from("- ");
+            if commit.breaking {
+                entry.push_str("**Breaking:** ");
+            }
+            if let Some(scope) = &commit.scope {
+                entry.push_str(&format!("**{scope}:** "));
+            }
+            entry.push_str(&commit.description);
+            match &web {
+                Some(web) => entry.push_str(&format!(" ([{hash}]({web}/commit/{hash}))")),
+                None => entry.push_str(&format!(" ({hash})")),
+            }
+            release.add(section, entry);
+        }
+    }
+
+    /// Returns the commit type to section mapping, with `curator.json` overriding the defaults
+    ///
+    /// An empty section leaves that type out of the changelog.
+    pub fn mapping() -> HashMap<String, String> {
+        let mut mapping: HashMap<String, String> = DEFAULT_MAPPING
+            .iter()
+            .map(|(kind, section)| (kind.to_string(), section.to_string()))
+            .collect();
+        let config_guard = CONFIGURATION.lock().unwrap();
+        if let Some(custom) = config_guard.get(MAPPING_SECTION) {
+            mapping.extend(
+                custom
+                    .iter()
+                    .map(|(kind, section)| (kind.to_lowercase(), section.clone())),
+            );
+        }
+        mapping
+    }
+
+    /// Returns `v` when release tags use it, which is also the default
+    fn tag_prefix() -> String {
+        match Git::semver_tags().first() {
+            Some((tag, _)) if !tag.starts_with('v') => String::new(),
+            _ => "v".to_string(),
+        }
+    }
+
+    /// Today's date as used in release headings
+    fn today() -> String {
+        Local::now().format("%Y-%m-%d").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "# Changelog
+
+## [Unreleased]
+
+### Added
+
+- Dark mode
+
+## [1.1.0] - 2024-03-01
+
+### Added
+
+- Export to CSV
+  with headers
+
+### Fixed
+
+- Crash on empty input
+
+## [1.0.0] - 2024-01-01
+
+- Initial release
+
+[Unreleased]: https://github.com/acme/app/compare/v1.1.0...HEAD
+[1.1.0]: https://github.com/acme/app/compare/v1.0.0...v1.1.0
+";
+
+    fn entries<'a>(release: &'a Release, section: &str) -> &'a [String] {
+        release
+            .sections
+            .iter()
+            .find(|(name, _)| name == section)
+            .map(|(_, entries)| entries.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn release(version: &str, section: &str, entries: &[&str]) -> Release {
+        let mut release = Release::new(version, None);
+        for entry in entries {
+            release.add(section, entry.to_string());
+        }
+        release
+    }
+
+    #[test]
+    fn parse_reads_releases_and_drops_links() {
+        let changelog = Changelog::parse(TEXT);
+        assert_eq!(changelog.header, "# Changelog");
+        let versions: Vec<&str> = changelog
+            .releases
+            .iter()
+            .map(|release| release.version.as_str())
+            .collect();
+        assert_eq!(versions, ["Unreleased", "1.1.0", "1.0.0"]);
+        assert_eq!(changelog.releases[1].date.as_deref(), Some("2024-03-01"));
+        assert_eq!(
+            entries(&changelog.releases[1], "Added"),
+            ["- Export to CSV\n  with headers"]
+        );
+        assert_eq!(entries(&changelog.releases[2], ""), ["- Initial release"]);
+        assert!(changelog
+            .releases
+            .iter()
+            .flat_map(|release| release.sections.iter())
+            .all(|(_, entries)| entries.iter().all(|entry| !entry.starts_with('['))));
+    }
+
+    #[test]
+    fn merge_skips_entries_from_existing_releases() {
+        let mut changelog = Changelog::parse(TEXT);
+        let unreleased = release(
+            "Unreleased",
+            "Fixed",
+            &["- Crash on empty input", "- Wrong totals"],
+        );
+        changelog.merge(unreleased, Vec::new());
+        assert_eq!(entries(&changelog.releases[0], "Fixed"), ["- Wrong totals"]);
+        assert_eq!(entries(&changelog.releases[0], "Added"), ["- Dark mode"]);
+    }
+
+    #[test]
+    fn merge_moves_generated_releases_out_of_unreleased() {
+        let mut changelog = Changelog::parse(TEXT);
+        let generated = release("1.2.0", "Added", &["- Dark mode"]);
+        changelog.merge(Release::new("Unreleased", None), vec![generated]);
+        let versions: Vec<&str> = changelog
+            .releases
+            .iter()
+            .map(|release| release.version.as_str())
+            .collect();
+        assert_eq!(versions, ["Unreleased", "1.2.0", "1.1.0", "1.0.0"]);
+        assert!(changelog.releases[0].is_empty());
+    }
+
+    #[test]
+    fn add_keeps_keep_a_changelog_section_order() {
+        let mut release = Release::new("Unreleased", None);
+        release.add("Fixed", "- b".to_string());
+        release.add("Added", "- a".to_string());
+        release.add("Added", "- a".to_string());
+        let sections: Vec<&str> = release
+            .sections
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(sections, ["Added", "Fixed"]);
+        assert_eq!(entries(&release, "Added"), ["- a"]);
+    }
+
+    #[test]
+    fn conventional_commit_parses_scope_and_breaking() {
+        let commit = ConventionalCommit::parse("feat(api)!: drop v1", "").unwrap();
+        assert_eq!(commit.kind, "feat");
+        assert_eq!(commit.scope.as_deref(), Some("api"));
+        assert!(commit.breaking);
+        assert_eq!(commit.description, "drop v1");
+
+        let commit =
+            ConventionalCommit::parse("fix: handle nulls", "BREAKING CHANGE: new format").unwrap();
+        assert!(commit.breaking);
+        assert!(ConventionalCommit::parse("Merge branch 'main'", "").is_none());
+    }
+}
@@ -0,0 +1,552 @@
+use crossterm::style::Stylize;
+use dialoguer::Input;
+use regex::Regex;
+use serde_json::Value;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::git::Git;
+use crate::tools::Tools;
+
+const CITATION: &str = "CITATION.cff";
+
+/// Section of `curator.json` holding the DOI and the ORCID of each author
+const CITATION_SECTION: &str = "citation";
+
+/// The CFF 1.2.0 JSON schema that `cu citation check` validates against
+const SCHEMA: &str = include_str!("../templates/citation/schema-1.2.0.json");
+
+/// Company suffixes that mark an author as an organization, compared without dots
+const LEGAL_FORMS: &[&str] = &[
+    "inc",
+    "llc",
+    "llp",
+    "ltd",
+    "limited",
+    "gmbh",
+    "ag",
+    "kg",
+    "ug",
+    "corp",
+    "corporation",
+    "co",
+    "plc",
+    "pty",
+    "sa",
+    "sas",
+    "sarl",
+    "srl",
+    "spa",
+    "bv",
+    "nv",
+    "ab",
+    "oy",
+    "as",
+    "se",
+];
+
+/// Words that mark an author as a group rather than a person
+const ORGANIZATION_WORDS: &[&str] = &[
+    "association",
+    "authors",
+    "company",
+    "consortium",
+    "contributors",
+    "developers",
+    "foundation",
+    "group",
+    "institute",
+    "lab",
+    "laboratory",
+    "labs",
+    "organisation",
+    "organization",
+    "project",
+    "society",
+    "team",
+    "university",
+];
+
+/// Enums longer than this, such as the SPDX license list, are not printed in errors
+const MAX_LISTED_VALUES: usize = 10;
+
+const ORCID_PATTERN: &str = r"^https://orcid\.org/[0-9]{4}-[0-9]{4}-[0-9]{4}-[0-9]{3}[0-9X]$";
+/// The bundled schema's `doi` pattern, so DOIs accepted at the prompt pass `cu citation check`
+const DOI_PATTERN: &str = r"^10\.\d{4,9}(\.\d+)?/[A-Za-z0-9:/_;\-\.\(\)\[\]\\]+$";
+
+pub struct CitationManager;
+
+impl CitationManager {
+    /// Creates `CITATION.cff` from `curator.json`, asking for ORCIDs and the DOI
+    pub fn set_citation() {
+        ConfigManager::load_config();
+        let (title, holders, license, mut stored) = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            let settings = config_guard.get("settings").cloned().unwrap_or_default();
+            let data = config_guard.get("data").cloned().unwrap_or_default();
+            (
+                settings.get("project").cloned().unwrap_or_default(),
+                data.get("copyright holders")
+                    .or_else(|| settings.get("author"))
+                    .cloned()
+                    .unwrap_or_default(),
+                data.get("license").cloned(),
+                config_guard
+                    .get(CITATION_SECTION)
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+        };
+
+        let mut authors = String::new();
+        for name in Self::split_authors(&holders) {
+            let parts: Vec<&str> = name.split_whitespace().collect();
+            if parts.len() < 2 || Self::is_organization(&name) {
+                authors.push_str(&format!("  - name: {}\n", Self::quote(&name)));
+                continue;
+            }
+            let (family, given) = parts.split_last().unwrap();
+            authors.push_str(&format!(
+                "  - family-names: {}\n    given-names: {}\n",
+                Self::quote(family),
+                Self::quote(&given.join(" "))
+            ));
+            let orcid = Self::ask(
+                &format!("ORCID of {name} (optional)"),
+                stored.get(&name).cloned(),
+                ORCID_PATTERN,
+                "an ORCID like https://orcid.org/0000-0002-1825-0097",
+            );
+            if let Some(orcid) = orcid {
+                authors.push_str(&format!("    orcid: {}\n", Self::quote(&orcid)));
+                stored.insert(name.clone(), orcid);
+            }
+        }
+        if authors.is_empty() {
+            eprintln!(
+                "{} No authors found. Set `copyright holders` in the data section of curator.json.",
+                "[ERROR]".red()
+            );
+            process::exit(1);
+        }
+        let doi = Self::ask(
+            "DOI (optional)",
+            stored.get("doi").cloned(),
+            DOI_PATTERN,
+            "a DOI like 10.5281/zenodo.1234567",
+        );
+        if let Some(doi) = &doi {
+            stored.insert("doi".to_string(), doi.clone());
+        }
+
+        let mut text = String::from(
+            "cff-version: 1.2.0\nmessage: \"If you use this software, please cite it using the metadata from this file.\"\ntype: software\n",
+        );
+        text.push_str(&format!("title: {}\n", Self::quote(&title)));
+        text.push_str(&format!("authors:\n{authors}"));
+        if let Some((tag, _)) = Git::semver_tags().first() {
+            text.push_str(&format!(
+                "version: {}\n",
+                Self::quote(tag.trim_start_matches('v'))
+            ));
+            if let Some(date) = Git::run(&["log", "-1", "--format=%as", tag]) {
+                text.push_str(&format!("date-released: {}\n", Self::quote(&date)));
+            }
+        }
+        if let Some(doi) = doi {
+            text.push_str(&format!("doi: {}\n", Self::quote(&doi)));
+        }
+        if let Some(license) = license {
+            text.push_str(&format!("license: {}\n", Self::quote(&license)));
+        }
+        if let Some(url) = Git::web_url() {
+            text.push_str(&format!("repository-code: {}\n", Self::quote(&url)));
+        }
+
+        {
+            let mut config_guard = CONFIGURATION.lock().unwrap();
+            config_guard.insert(CITATION_SECTION.to_string(), stored);
+        }
+        ConfigManager::save_config();
+        match Tools::write_file(CITATION, &text) {
+            Ok(()) => println!("{} Created {}", "[SUCCESS]".green(), CITATION),
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Validates `CITATION.cff` against the bundled CFF 1.2.0 schema
+    pub fn check_citation() {
+        let text = match std::fs::read_to_string(CITATION) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{} Failed to read {}: {}", "[ERROR]".red(), CITATION, e);
+                println!(
+                    "{} Run `{}` to create it",
+                    "[FIX]".green(),
+                    "cu citation set".grey()
+                );
+                process::exit(1);
+            }
+        };
+        let errors = match Self::validate(&text) {
+            Ok(errors) => errors,
+            Err(e) => {
+                eprintln!("{} {} is not valid YAML: {}", "[ERROR]".red(), CITATION, e);
+                process::exit(1);
+            }
+        };
+        if errors.is_empty() {
+            println!(
+                "{} {} is valid Citation File Format 1.2.0",
+                "[SUCCESS]".green(),
+                CITATION
+            );
+            return;
+        }
+        for error in &errors {
+            println!("{} {}", "✗".red(), error);
+        }
+        eprintln!(
+            "{} {} has {} error{}",
+            "[ERROR]".red(),
+            CITATION,
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" }
+        );
+        process::exit(1);
+    }
+
+    /// Parses CFF text and returns the schema violations as `path: problem`
+    pub fn validate(text: &str) -> Result<Vec<String>, serde_norway::Error> {
+        let document: Value = serde_norway::from_str(text)?;
+        let schema: Value = serde_json::from_str(SCHEMA).expect("bundled schema is valid JSON");
+        let mut errors = Vec::new();
+        Self::check(&document, &schema, &schema, "", &mut errors);
+        Ok(errors)
+    }
+
+    /// Checks a value against a schema node, supporting the keywords the bundled schema uses
+    fn check(value: &Value, node: &Value, root: &Value, path: &str, errors: &mut Vec<String>) {
+        let at = if path.is_empty() { "(root)" } else { path };
+        if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+            let name = reference.trim_start_matches("#/definitions/");
+            if let Some(definition) = root.get("definitions").and_then(|d| d.get(name)) {
+                Self::check(value, definition, root, path, errors);
+            }
+            return;
+        }
+        if let Some(options) = node.get("anyOf").and_then(Value::as_array) {
+            // Report the problems of the form that came closest to matching
+            let closest = options
+                .iter()
+                .map(|option| {
+                    let mut option_errors = Vec::new();
+                    Self::check(value, option, root, path, &mut option_errors);
+                    option_errors
+                })
+                .min_by_key(Vec::len);
+            errors.extend(closest.unwrap_or_default());
+            return;
+        }
+        if let Some(types) = node.get("type") {
+            let types: Vec<&str> = match types {
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                other => other.as_str().into_iter().collect(),
+            };
+            let actual = match value {
+                Value::Null => "null",
+                Value::Bool(_) => "boolean",
+                Value::Number(_) => "number",
+                Value::String(_) => "string",
+                Value::Array(_) => "array",
+                Value::Object(_) => "object",
+            };
+            let integer = value.is_i64() || value.is_u64();
+            let matches = types.contains(&actual) || (integer && types.contains(&"integer"));
+            if !matches {
+                errors.push(format!(
+                    "{at}: expected {}, found {actual}",
+                    types.join(" or ")
+                ));
+                return;
+            }
+        }
+        if let Some(allowed) = node.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                if allowed.len() > MAX_LISTED_VALUES {
+                    errors.push(format!("{at}: {value} is not an allowed value"));
+                } else {
+                    let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+                    errors.push(format!("{at}: must be one of {}", allowed.join(", ")));
+                }
+            }
+        }
+        if let Some(number) = value.as_f64() {
+            if let Some(min) = node.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    errors.push(format!("{at}: must be at least {min}"));
+                }
+            }
+            if let Some(max) = node.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    errors.push(format!("{at}: must be at most {max}"));
+                }
+            }
+        }
+        if let Value::String(text) = value {
+            if let Some(min) = node.get("minLength").and_then(Value::as_u64) {
+                if (text.chars().count() as u64) < min {
+                    errors.push(format!("{at}: must not be empty"));
+                }
+            }
+            if let Some(pattern) = node.get("pattern").and_then(Value::as_str) {
+                match Regex::new(pattern) {
+                    Ok(regex) if !regex.is_match(text) => {
+                        errors.push(format!("{at}: '{text}' does not match {pattern}"));
+                    }
+                    Ok(_) => {}
+                    Err(e) => errors.push(format!("{at}: cannot check pattern {pattern}: {e}")),
+                }
+            }
+        }
+        if let Value::Array(items) = value {
+            if let Some(min) = node.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    errors.push(format!("{at}: needs at least {min} item(s)"));
+                }
+            }
+            if node.get("uniqueItems") == Some(&Value::Bool(true)) {
+                for (i, item) in items.iter().enumerate() {
+                    if items[..i].contains(item) {
+                        errors.push(format!("{at}[{i}]: duplicates an earlier item"));
+                    }
+                }
+            }
+            if let Some(schema) = node.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    Self::check(item, schema, root, &format!("{path}[{i}]"), errors);
+                }
+            }
+        }
+        if let Value::Object(map) = value {
+            for key in node
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                if !map.contains_key(key) {
+                    errors.push(format!("{at}: missing required key '{key}'"));
+                }
+            }
+            let properties = node.get("properties").and_then(Value::as_object);
+            for (key, item) in map {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(schema) => Self::check(item, schema, root, &child, errors),
+                    None if node.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                        errors.push(format!("{at}: unknown key '{key}'"))
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    /// Splits `Jane Doe, John Roe and Acme, Inc.` into single names
+    ///
+    /// A legal form such as `Inc.` after a comma stays with the name before it.
+    fn split_authors(holders: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for part in holders
+            .split([',', '&'])
+            .flat_map(|part| part.split(" and "))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let legal_form = part
+                .split_whitespace()
+                .all(|word| LEGAL_FORMS.contains(&Self::normalize_word(word).as_str()));
+            match names.last_mut() {
+                Some(last) if legal_form => {
+                    last.push_str(", ");
+                    last.push_str(part);
+                }
+                _ => names.push(part.to_string()),
+            }
+        }
+        names
+    }
+
+    /// Tells organizations such as `Acme Inc.` or `The Rust Team` apart from people
+    fn is_organization(name: &str) -> bool {
+        name.split_whitespace()
+            .map(Self::normalize_word)
+            .any(|word| {
+                LEGAL_FORMS.contains(&word.as_str()) || ORGANIZATION_WORDS.contains(&word.as_str())
+            })
+    }
+
+    fn normalize_word(word: &str) -> String {
+        word.trim_matches(|c: char| !c.is_alphanumeric())
+            .replace('.', "")
+            .to_lowercase()
+    }
+
+    /// Asks for an optional value that must match `pattern`
+    fn ask(prompt: &str, default: Option<String>, pattern: &str, example: &str) -> Option<String> {
+        let regex = Regex::new(pattern).expect("valid pattern");
+        let example = example.to_string();
+        let mut input = Input::<String>::new()
+            .with_prompt(prompt)
+            .allow_empty(true)
+            .validate_with(move |value: &String| {
+                if value.trim().is_empty() || regex.is_match(value.trim()) {
+                    Ok(())
+                } else {
+                    Err(format!("Expected {example}"))
+                }
+            });
+        if let Some(default) = default {
+            input = input.default(default);
+        }
+        let value = input.interact_text().unwrap().trim().to_string();
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Quotes a string as a double-quoted YAML scalar
+    fn quote(text: &str) -> String {
+        serde_json::to_string(text).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = r#"cff-version: 1.2.0
+message: "If you use this software, please cite it as below."
+title: "curator"
+version: 1.2.0
+date-released: 2024-03-01
+license: MIT
+authors:
+  - family-names: "Doe"
+    given-names: "Jane"
+    orcid: "https://orcid.org/0000-0002-1825-0097"
+  - name: "Acme, Inc."
+    region: "Bavaria"
+    tel: "+49 89 123456"
+    date-start: 2020-01-01
+"#;
+
+    #[test]
+    fn validate_accepts_a_complete_citation() {
+        assert_eq!(
+            CitationManager::validate(VALID).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn validate_reports_missing_and_invalid_fields() {
+        let text = VALID
+            .replace("title: \"curator\"\n", "")
+            .replace("license: MIT", "license: Not-A-License")
+            .replace("2024-03-01", "March 2024");
+        let errors = CitationManager::validate(&text).unwrap();
+        assert!(
+            errors.iter().any(|error| error.contains("title")),
+            "{errors:?}"
+        );
+        assert!(
+            errors.iter().any(|error| error.starts_with("license")),
+            "{errors:?}"
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|error| error.starts_with("date-released")),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_unknown_keys_and_bad_orcids() {
+        let text = VALID
+            .replace("0000-0002-1825-0097", "1234")
+            .replace("version: 1.2.0\n", "version: 1.2.0\nstars: 5\n");
+        let errors = CitationManager::validate(&text).unwrap();
+        assert!(
+            errors.iter().any(|error| error.contains("stars")),
+            "{errors:?}"
+        );
+        assert!(
+            errors.iter().any(|error| error.contains("orcid")),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn validate_fails_on_malformed_yaml() {
+        assert!(CitationManager::validate("authors: [unclosed").is_err());
+    }
+
+    #[test]
+    fn split_authors_keeps_legal_forms_with_their_name() {
+        assert_eq!(
+            CitationManager::split_authors("Jane Doe, John Roe and Acme, Inc."),
+            ["Jane Doe", "John Roe", "Acme, Inc."]
+        );
+        assert_eq!(
+            CitationManager::split_authors("Foo GmbH & Bar"),
+            ["Foo GmbH", "Bar"]
+        );
+    }
+
+    #[test]
+    fn is_organization_tells_entities_from_people() {
+        assert!(CitationManager::is_organization("Acme, Inc."));
+        assert!(CitationManager::is_organization("The Rust Team"));
+        assert!(CitationManager::is_organization("Example Foundation"));
+        assert!(!CitationManager::is_organization("Jane Doe"));
+        assert!(!CitationManager::is_organization("Colin Incandenza"));
+    }
+
+    #[test]
+    fn doi_prompt_uses_the_schema_pattern() {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        assert_eq!(schema["definitions"]["doi"]["pattern"], DOI_PATTERN);
+    }
+
+    #[test]
+    fn every_schema_pattern_compiles() {
+        fn patterns<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(pattern)) = map.get("pattern") {
+                        found.push(pattern);
+                    }
+                    map.values().for_each(|value| patterns(value, found));
+                }
+                Value::Array(items) => items.iter().for_each(|value| patterns(value, found)),
+                _ => {}
+            }
+        }
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        let mut found = Vec::new();
+        patterns(&schema, &mut found);
+        assert!(found.len() >= 10);
+        for pattern in found {
+            Regex::new(pattern).unwrap_or_else(|e| panic!("{pattern}: {e}"));
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use crossterm::style::Stylize;
+use dialoguer::Input;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::git::Git;
+use crate::standards::StandardsReport;
+use crate::tools::Tools;
+
+/// Section of `curator.json` mapping commit emails to forge usernames
+const MAPPING_SECTION: &str = "codeowners";
+
+/// Share of a directory's commits an author needs to be proposed as an owner
+const MIN_OWNER_SHARE: f64 = 0.2;
+
+/// A CODEOWNERS rule: a gitignore-style pattern and its owners
+#[derive(Clone, Debug)]
+pub struct OwnerRule {
+    pub line: usize,
+    pub pattern: String,
+    pub owners: Vec<String>,
+}
+
+pub struct CodeownersManager;
+
+impl CodeownersManager {
+    /// Proposes a CODEOWNERS file from the authors of each directory and writes it after confirmation
+    pub fn generate_codeowners(since: &str, depth: usize, max_owners: usize, yes: bool) {
+        ConfigManager::load_config();
+        let Some(text) = Self::propose(since, depth, max_owners) else {
+            eprintln!(
+                "{} No commits found since {}. CODEOWNERS is built from the git history.",
+                "[ERROR]".red(),
+                since
+            );
+            process::exit(1);
+        };
+        let path = Self::location();
+        println!("{}", format!("Proposed {path}:").bold());
+        println!("{text}");
+        if !yes {
+            let write = Input::<String>::new()
+                .with_prompt(format!("Write {path}? (y/n)"))
+                .default("y".into())
+                .interact_text()
+                .unwrap()
+                .to_lowercase()
+                .starts_with('y');
+            if !write {
+                return;
+            }
+        }
+        if let Err(e) = Self::write(&path, &text) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+
+    /// Generates CODEOWNERS for `cu standards fix` from the last year of history
+    pub fn generate(interactive: bool) -> Result<(), String> {
+        match Self::propose("1 year ago", 1, 2) {
+            Some(text) => {
+                if interactive {
+                    println!("{text}");
+                }
+                Self::write(&Self::location(), &text)
+            }
+            None => {
+                println!(
+                    "{} No commits in the last year, skipping CODEOWNERS.",
+                    "[INFO]".yellow()
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Maps a commit email to a forge username in `curator.json`
+    pub fn map_owner(email: String, username: String) {
+        ConfigManager::load_config();
+        let username = if username.starts_with('@') {
+            username
+        } else {
+            format!("@{username}")
+        };
+        {
+            let mut config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .entry(MAPPING_SECTION.to_string())
+                .or_default()
+                .insert(email.to_lowercase(), username.clone());
+        }
+        ConfigManager::save_config();
+        println!("{} Mapped {} to {}", "[SUCCESS]".green(), email, username);
+    }
+
+    /// Lists tracked files that no CODEOWNERS rule assigns an owner to
+    pub fn check_codeowners() {
+        let Some(path) = Self::existing() else {
+            eprintln!("{} No CODEOWNERS file found.", "[ERROR]".red());
+            println!(
+                "{} Run `{}` to create one",
+                "[FIX]".green(),
+                "cu codeowners generate".grey()
+            );
+            process::exit(1);
+        };
+        let text = std::fs::read_to_string(&path).unwrap_or_default();
+        let rules = Self::parse(&text);
+        let Some(files) = Git::run(&["ls-files"]) else {
+            eprintln!("{} Not a git repository.", "[ERROR]".red());
+            process::exit(1);
+        };
+        let files: Vec<&str> = files.lines().filter(|file| !file.is_empty()).collect();
+        let unowned = Self::unowned(&rules, &files);
+
+        if unowned.is_empty() {
+            println!(
+                "{} All {} tracked files have an owner in {}",
+                "[SUCCESS]".green(),
+                files.len(),
+                path
+            );
+            return;
+        }
+        for (file, rule) in &unowned {
+            match rule {
+                Some(rule) => println!(
+                    "{} {} {}",
+                    "✗".red(),
+                    file,
+                    format!("(line {}: `{}` has no owners)", rule.line, rule.pattern).grey()
+                ),
+                None => println!("{} {}", "✗".red(), file),
+            }
+        }
+        eprintln!(
+            "{} {} of {} tracked files have no owner in {}",
+            "[ERROR]".red(),
+            unowned.len(),
+            files.len(),
+            path
+        );
+        process::exit(1);
+    }
+
+    /// Returns the files without owners, with the ownerless rule that matched them, if any
+    pub fn unowned<'a>(
+        rules: &'a [OwnerRule],
+        files: &[&str],
+    ) -> Vec<(String, Option<&'a OwnerRule>)> {
+        files
+            .iter()
+            .filter_map(|file| {
+                match rules
+                    .iter()
+                    .rev()
+                    .find(|rule| Self::matches(&rule.pattern, file))
+                {
+                    Some(rule) if !rule.owners.is_empty() => None,
+                    rule => Some((file.to_string(), rule)),
+                }
+            })
+            .collect()
+    }
+
+    /// Parses the rules of a CODEOWNERS file, skipping comments and blank lines
+    pub fn parse(text: &str) -> Vec<OwnerRule> {
+        text.lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let line = line.split_once(" #").map_or(line, |(rule, _)| rule).trim();
+                if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+                    return None;
+                }
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?.to_string();
+                Some(OwnerRule {
+                    line: i + 1,
+                    pattern,
+                    owners: parts.map(str::to_string).collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Checks a gitignore-style CODEOWNERS pattern against a path relative to the root
+    ///
+    /// Patterns with a leading or inner `/` are anchored to the root, others match at any depth.
+    /// A pattern matching a directory matches everything below it.
+    pub fn matches(pattern: &str, file: &str) -> bool {
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        if pattern.is_empty() {
+            return true;
+        }
+
+        let components: Vec<&str> = file.split('/').collect();
+        let candidates = if dir_only {
+            components.len() - 1
+        } else {
+            components.len()
+        };
+        (1..=candidates).any(|end| {
+            if anchored {
+                Tools::glob_match(pattern, &components[..end].join("/"))
+            } else {
+                Tools::glob_match(pattern, components[end - 1])
+            }
+        })
+    }
+
+    /// Builds CODEOWNERS text from who committed to each directory since `since`
+    fn propose(since: &str, depth: usize, max_owners: usize) -> Option<String> {
+        let log = Git::run(&[
+            "log",
+            "--no-merges",
+            &format!("--since={since}"),
+            "--format=%x00%aE",
+            "--name-only",
+        ])?;
+
+        let mut overall: HashMap<String, usize> = HashMap::new();
+        let mut per_dir: BTreeMap<String, HashMap<String, usize>> = BTreeMap::new();
+        let mut commits = 0;
+        for commit in log.split('\0').filter(|commit| !commit.trim().is_empty()) {
+            let mut lines = commit.lines();
+            let Some(email) = lines.next().map(|email| email.trim().to_lowercase()) else {
+                continue;
+            };
+            commits += 1;
+            *overall.entry(email.clone()).or_default() += 1;
+            let dirs: HashSet<String> = lines
+                .filter(|file| !file.trim().is_empty())
+                .filter_map(|file| {
+                    let components: Vec<&str> = file.split('/').collect();
+                    let keep = depth.min(components.len() - 1);
+                    (keep > 0).then(|| components[..keep].join("/"))
+                })
+                .collect();
+            for dir in dirs {
+                *per_dir
+                    .entry(dir)
+                    .or_default()
+                    .entry(email.clone())
+                    .or_default() += 1;
+            }
+        }
+        if commits == 0 {
+            return None;
+        }
+
+        let mapping = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .get(MAPPING_SECTION)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let mut unmapped: HashSet<String> = HashSet::new();
+        let mut owners_of = |counts: &HashMap<String, usize>| {
+            let total: usize = counts.values().sum();
+            let mut ranked: Vec<(&String, &usize)> = counts.iter().collect();
+            ranked.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            ranked
+                .into_iter()
+                .enumerate()
+                .filter(|(i, (_, count))| {
+                    *i == 0 || **count as f64 / total as f64 >= MIN_OWNER_SHARE
+                })
+                .take(max_owners.max(1))
+                .map(|(_, (email, _))| match mapping.get(email) {
+                    Some(username) => username.clone(),
+                    None => {
+                        unmapped.insert(email.clone());
+                        email.clone()
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        let mut rules = vec![("*".to_string(), owners_of(&overall))];
+        for (dir, counts) in &per_dir {
+            if Path::new(dir).is_dir() {
+                rules.push((format!("/{dir}/"), owners_of(counts)));
+            }
+        }
+
+        let width = rules
+            .iter()
+            .map(|(pattern, _)| pattern.len())
+            .max()
+            .unwrap_or(1);
+        let mut text = format!(
+            "# Generated by curator from the git history since {since}.\n# The last matching pattern takes precedence.\n\n"
+        );
+        for (pattern, owners) in rules {
+            text.push_str(&format!("{pattern:<width$} {owners}\n"));
+        }
+
+        let mut unmapped: Vec<String> = unmapped.into_iter().collect();
+        unmapped.sort();
+        for email in unmapped {
+            println!(
+                "{} {} has no username. Run `{}` to use one instead of the email.",
+                "[INFO]".yellow(),
+                email,
+                format!("cu codeowners map {email} <username>").grey()
+            );
+        }
+        Some(text)
+    }
+
+    /// Returns the existing CODEOWNERS file, if any
+    fn existing() -> Option<String> {
+        StandardsReport::evaluate(Path::new("."))
+            .checks
+            .into_iter()
+            .find(|check| check.id == "codeowners")
+            .and_then(|check| check.path)
+    }
+
+    /// Where CODEOWNERS is written: the existing file or `.github/CODEOWNERS`
+    fn location() -> String {
+        Self::existing().unwrap_or_else(|| ".github/CODEOWNERS".to_string())
+    }
+
+    fn write(path: &str, text: &str) -> Result<(), String> {
+        Tools::write_file(path, text)?;
+        println!("{} Created {}", "[SUCCESS]".green(), path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchored_directory_pattern_matches_everything_below() {
+        assert!(CodeownersManager::matches("/docs/", "docs/guide.md"));
+        assert!(CodeownersManager::matches("/docs/", "docs/api/index.md"));
+        assert!(!CodeownersManager::matches("/docs/", "src/docs/guide.md"));
+        assert!(!CodeownersManager::matches("/docs/", "docs"));
+    }
+
+    #[test]
+    fn unanchored_pattern_matches_at_any_depth() {
+        assert!(CodeownersManager::matches("*.rs", "main.rs"));
+        assert!(CodeownersManager::matches("*.rs", "src/deep/lib.rs"));
+        assert!(!CodeownersManager::matches("*.rs", "src/lib.rsx"));
+        assert!(CodeownersManager::matches("docs/", "site/docs/index.md"));
+    }
+
+    #[test]
+    fn double_star_spans_directories() {
+        assert!(CodeownersManager::matches("src/**/mod.rs", "src/mod.rs"));
+        assert!(CodeownersManager::matches(
+            "src/**/mod.rs",
+            "src/a/b/mod.rs"
+        ));
+        assert!(!CodeownersManager::matches("src/**/mod.rs", "lib/a/mod.rs"));
+        assert!(!CodeownersManager::matches(
+            "src/**/mod.rs",
+            "src/a/mod.rsx"
+        ));
+    }
+
+    #[test]
+    fn parse_skips_comments_blank_lines_and_sections() {
+        let rules = CodeownersManager::parse(
+            "# Owners\n\n* @acme/core\n[Docs]\n/docs/ @jane @acme/docs # writers\n  \n/vendor/\n",
+        );
+        let parsed: Vec<(usize, &str, Vec<&str>)> = rules
+            .iter()
+            .map(|rule| {
+                (
+                    rule.line,
+                    rule.pattern.as_str(),
+                    rule.owners.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                (3, "*", vec!["@acme/core"]),
+                (5, "/docs/", vec!["@jane", "@acme/docs"]),
+                (7, "/vendor/", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn last_matching_rule_decides_ownership() {
+        let rules = CodeownersManager::parse("* @acme/core\n/vendor/\n/docs/ @jane\n");
+        let unowned =
+            CodeownersManager::unowned(&rules, &["src/lib.rs", "vendor/x.c", "docs/a.md"]);
+        let unowned: Vec<(&str, Option<usize>)> = unowned
+            .iter()
+            .map(|(file, rule)| (file.as_str(), rule.map(|rule| rule.line)))
+            .collect();
+        assert_eq!(unowned, [("vendor/x.c", Some(2))]);
+    }
+}
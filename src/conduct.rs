@@ -5,6 +5,8 @@ use reqwest::blocking::Client;
 use std::process;
 
 use crate::config::{ConfigManager, CONFIGURATION};
+use crate::store::{StoreScope, TemplateStore};
+use crate::tools::Tools;
 use crate::LicenseManager;
 
 static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
@@ -15,11 +17,324 @@ static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
         .expect("Failed to create HTTP client")
 });
 
+/// Contributor Covenant version written before the chosen version was stored in `curator.json`
+const LEGACY_COVENANT_VERSION: &str = "2.0";
+
+/// One version and language of a code of conduct
+#[derive(Clone, Debug)]
+pub struct ConductVariant {
+    pub version: String,
+    /// Language code such as `en` or `de`
+    pub language: String,
+    /// Where an updated copy of the template is fetched from with `--latest`
+    pub source: String,
+    /// Template text compiled into the binary
+    pub bundled: Option<&'static str>,
+}
+
+/// A code of conduct that can be installed into a project
+#[derive(Clone, Debug)]
+pub struct ConductInfo {
+    /// Identifier used on the command line and in `curator.json`
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    /// Advice shown after the code has been installed
+    pub note: Option<String>,
+    pub variants: Vec<ConductVariant>,
+}
+
+impl ConductInfo {
+    /// Returns the versions of the code, newest first
+    pub fn versions(&self) -> Vec<&str> {
+        let mut versions: Vec<&str> = Vec::new();
+        for variant in &self.variants {
+            if !versions.contains(&variant.version.as_str()) {
+                versions.push(&variant.version);
+            }
+        }
+        versions.sort_by(|a, b| Tools::compare_versions(b, a));
+        versions
+    }
+
+    /// Returns the newest version of the code
+    pub fn latest_version(&self) -> &str {
+        self.versions().first().copied().unwrap_or_default()
+    }
+
+    /// Returns the languages a version is available in
+    pub fn languages(&self, version: &str) -> Vec<&str> {
+        self.variants
+            .iter()
+            .filter(|variant| variant.version == version)
+            .map(|variant| variant.language.as_str())
+            .collect()
+    }
+
+    /// Finds a variant, defaulting to the newest version and to English
+    pub fn variant(
+        &self,
+        version: Option<&str>,
+        language: Option<&str>,
+    ) -> Result<&ConductVariant, String> {
+        let language = language.map(|language| language.trim().to_lowercase());
+        let version = match version {
+            Some(version) => version.trim().trim_start_matches('v').to_string(),
+            None => match &language {
+                Some(language) => self
+                    .versions()
+                    .into_iter()
+                    .find(|version| self.languages(version).contains(&language.as_str()))
+                    .ok_or_else(|| format!("{} is not available in '{}'", self.title, language))?
+                    .to_string(),
+                None => self.latest_version().to_string(),
+            },
+        };
+        let languages = self.languages(&version);
+        if languages.is_empty() {
+            return Err(format!(
+                "{} has no version '{}' (available: {})",
+                self.title,
+                version,
+                self.versions().join(", ")
+            ));
+        }
+        let language = language.unwrap_or_else(|| {
+            if languages.contains(&"en") {
+                "en".to_string()
+            } else {
+                languages[0].to_string()
+            }
+        });
+        self.variants
+            .iter()
+            .find(|variant| variant.version == version && variant.language == language)
+            .ok_or_else(|| {
+                format!(
+                    "{} {} is not available in '{}' (available: {})",
+                    self.title,
+                    version,
+                    language,
+                    languages.join(", ")
+                )
+            })
+    }
+}
+
+/// The codes of conduct available to `cu conduct`
+pub struct ConductRegistry {
+    conducts: Vec<ConductInfo>,
+}
+
+impl ConductRegistry {
+    /// Returns the registry of built-in codes of conduct
+    pub fn builtin() -> Self {
+        let variant = |version: &str, language: &str, file: &str, bundled: &'static str| {
+            ConductVariant {
+                version: version.to_string(),
+                language: language.to_string(),
+                source: format!(
+                    "https://raw.githubusercontent.com/Saturnyx/curator/refs/heads/main/templates/conduct/{file}.md"
+                ),
+                bundled: Some(bundled),
+            }
+        };
+        let conducts = vec![
+            ConductInfo {
+                id: "contributor-covenant".to_string(),
+                title: "Contributor Covenant Code of Conduct".to_string(),
+                description: "Suitable for projects of all sizes".to_string(),
+                note: None,
+                variants: vec![
+                    variant(
+                        "2.1",
+                        "en",
+                        "contributor-covenant-2.1",
+                        include_str!("../templates/conduct/contributor-covenant-2.1.md"),
+                    ),
+                    variant(
+                        "2.1",
+                        "de",
+                        "contributor-covenant-2.1-de",
+                        include_str!("../templates/conduct/contributor-covenant-2.1-de.md"),
+                    ),
+                    variant(
+                        "2.1",
+                        "ja",
+                        "contributor-covenant-2.1-ja",
+                        include_str!("../templates/conduct/contributor-covenant-2.1-ja.md"),
+                    ),
+                    variant(
+                        "2.0",
+                        "en",
+                        "contributor-covenant",
+                        include_str!("../templates/conduct/contributor-covenant.md"),
+                    ),
+                    variant(
+                        "1.4",
+                        "en",
+                        "contributor-covenant-1.4",
+                        include_str!("../templates/conduct/contributor-covenant-1.4.md"),
+                    ),
+                ],
+            },
+            ConductInfo {
+                id: "django".to_string(),
+                title: "Django Code of Conduct".to_string(),
+                description: "Suitable for large communities and events".to_string(),
+                note: None,
+                variants: vec![variant(
+                    "1.0",
+                    "en",
+                    "django",
+                    include_str!("../templates/conduct/django.md"),
+                )],
+            },
+            ConductInfo {
+                id: "citizen".to_string(),
+                title: "Citizen Code of Conduct".to_string(),
+                description: "Suitable for most projects".to_string(),
+                note: Some("NOTE: Every organization's governing policies should dictate how you handle warnings and expulsions of community members. It is strongly recommended that you mention those policies here or in Section 7 and that you include a mechanism for addressing grievances.".to_string()),
+                variants: vec![variant(
+                    "2.3",
+                    "en",
+                    "citizen",
+                    include_str!("../templates/conduct/citizen.md"),
+                )],
+            },
+        ];
+        ConductRegistry { conducts }
+    }
+
+    /// Returns the built-in codes followed by the custom codes in the template store
+    pub fn load() -> Self {
+        let mut registry = Self::builtin();
+        for (file_name, scope) in TemplateStore::list("conduct") {
+            let Some(id) = file_name.strip_suffix(".md") else {
+                continue;
+            };
+            if registry.get(id).is_some() {
+                continue;
+            }
+            let Some((path, _)) = TemplateStore::find("conduct", &file_name) else {
+                continue;
+            };
+            let title = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|text| {
+                    text.lines()
+                        .find_map(|line| line.strip_prefix("# ").map(|t| t.trim().to_string()))
+                })
+                .unwrap_or_else(|| id.to_string());
+            registry.conducts.push(ConductInfo {
+                id: id.to_lowercase(),
+                title,
+                description: format!("Custom code of conduct ({})", scope.label()),
+                note: None,
+                variants: vec![ConductVariant {
+                    version: "custom".to_string(),
+                    language: "en".to_string(),
+                    source: path.to_string_lossy().to_string(),
+                    bundled: None,
+                }],
+            });
+        }
+        registry
+    }
+
+    /// Checks if a code ships with curator
+    pub fn is_builtin(id: &str) -> bool {
+        Self::builtin().get(id).is_some()
+    }
+
+    /// Returns every code in the registry
+    pub fn all(&self) -> &[ConductInfo] {
+        &self.conducts
+    }
+
+    /// Finds a code by its id, ignoring case
+    pub fn get(&self, id: &str) -> Option<&ConductInfo> {
+        let id = id.trim().to_lowercase();
+        self.conducts.iter().find(|conduct| conduct.id == id)
+    }
+
+    /// Resolves a code by id, or exits with suggestions when it is unknown
+    pub fn resolve(&self, id: &str) -> &ConductInfo {
+        if let Some(conduct) = self.get(id) {
+            return conduct;
+        }
+        println!(
+            "{} Code of conduct '{}' not found. Please try again.",
+            "[ERROR]".red(),
+            id.to_string().red()
+        );
+        let ids: Vec<String> = self.conducts.iter().map(|c| c.id.clone()).collect();
+        let similar = Tools::fuzzy_search(&ids, &id.to_lowercase());
+        if !similar.is_empty() {
+            println!("{}", "Did you mean:".yellow());
+            for (i, (conduct, _score)) in similar.iter().enumerate() {
+                println!("  {}. {}", i + 1, conduct.cyan());
+            }
+        }
+        println!(
+            "{} Run `{}` to see all codes of conduct",
+            "[FIX]".green(),
+            "cu conduct list".grey()
+        );
+        process::exit(1);
+    }
+
+    /// Asks the user to pick a code from a numbered menu
+    pub fn prompt(&self) -> &ConductInfo {
+        println!("Would you prefer the:");
+        for (i, conduct) in self.conducts.iter().enumerate() {
+            println!("  {}. {} - {}", i + 1, conduct.title, conduct.description);
+        }
+        let choices = (1..=self.conducts.len())
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+        let choices = match choices.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+            None => String::new(),
+        };
+        loop {
+            let choice: String = Input::new()
+                .with_prompt(format!("Enter the number of your choice ({choices})"))
+                .interact_text()
+                .unwrap();
+
+            match choice.trim().parse::<usize>() {
+                Ok(n) if (1..=self.conducts.len()).contains(&n) => break &self.conducts[n - 1],
+                _ => {
+                    println!("{}", format!("Please enter {choices}").red());
+                    continue;
+                }
+            }
+        }
+    }
+
+    /// Picks the code given by `id`, or prompts when no id was given
+    pub fn select(&self, id: Option<String>) -> &ConductInfo {
+        match id {
+            Some(id) => self.resolve(&id),
+            None => self.prompt(),
+        }
+    }
+}
+
 pub struct ConductManager;
 
 impl ConductManager {
     /// Searches for the codes of conduct and saves to `CODE_OF_CONDUCT.md`
-    pub fn set_conduct() {
+    pub fn set_conduct(
+        id: Option<String>,
+        version: Option<String>,
+        language: Option<String>,
+        latest: bool,
+        from: Option<String>,
+        org: bool,
+    ) {
         if !ConfigManager::check_config() {
             println!(
                 "{} Project configuration not found or has been misconfigured",
@@ -35,29 +350,21 @@ impl ConductManager {
             ConfigManager::load_config();
         }
 
-        println!("Would you prefer the:");
-        println!("  1. Contributor Covenant Code of Conduct - Suitable for projects of all sizes");
-        println!("  2. Django Code of Conduct - Suitable for large communities and events");
-        println!("  3. Citizen Code of Conduct - Suitable for most projects");
-        let conduct = loop {
-            let choice: String = Input::new()
-                .with_prompt("Enter the number of your choice (1, 2 or 3)")
-                .interact_text()
-                .unwrap();
-
-            match choice.trim() {
-                "1" => break "Contributor Covenant",
-                "2" => break "Django",
-                "3" => break "Citizen",
-                _ => {
-                    println!("{}", "Please enter 1, 2 or 3".red());
-                    continue;
-                }
-            }
+        let id = match from {
+            Some(source) => Some(Self::add_custom_conduct(&source, id, org)),
+            None => id,
         };
+        let registry = ConductRegistry::load();
+        let conduct = registry.select(id);
+        let variant = Self::select_variant(conduct, version, language);
 
-        println!("You selected the {} Code of Conduct.", conduct.green());
-        Self::download_conduct(conduct.to_lowercase().replace(" ", "-")).unwrap_or_else(|e| {
+        println!(
+            "You selected the {} (version {}, {}).",
+            conduct.title.clone().green(),
+            variant.version,
+            variant.language
+        );
+        Self::download_conduct(conduct, variant, latest).unwrap_or_else(|e| {
             println!(
                 "{} Failed to download code of conduct: {}",
                 "[ERROR]".red(),
@@ -66,114 +373,517 @@ impl ConductManager {
             process::exit(1);
         });
 
-        if conduct == "Citizen" {
-            println!("NOTE: Every organization's governing policies should dictate how you handle warnings and expulsions of community members. It is strongly recommended that you mention those policies here or in Section 7 and that you include a mechanism for addressing grievances.");
+        if let Some(note) = &conduct.note {
+            println!("{note}");
         }
         {
             let mut config_guard = CONFIGURATION.lock().unwrap();
             if let Some(data) = config_guard.get_mut("data") {
-                data.insert(
-                    "conduct".to_string(),
-                    conduct.to_lowercase().replace(" ", "-"),
-                );
+                data.insert("conduct".to_string(), conduct.id.clone());
+                data.insert("conduct_version".to_string(), variant.version.clone());
+                data.insert("conduct_language".to_string(), variant.language.clone());
             }
         }
         ConfigManager::save_config();
     }
 
-    /// Removes the code of conduct
-    pub fn remove_conduct() {
-        if let Err(e) = std::fs::remove_file("CODE_OF_CONDUCT.md") {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                println!(
-                    "{} CODE_OF_CONDUCT.md file does not exist.",
-                    "[INFO]".yellow()
-                );
-            } else {
+    /// Regenerates the code of conduct from the choices stored in config, without prompting
+    pub fn reload_conduct() {
+        if !ConfigManager::check_config() {
+            println!(
+                "{} Project configuration not found or has been misconfigured",
+                "[ERROR]".red()
+            );
+            println!(
+                "{} Run `{}` to reconfigure project",
+                "[FIX]".green(),
+                "cu config set".grey()
+            );
+            process::exit(1)
+        } else {
+            ConfigManager::load_config();
+        }
+        let data = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard.get("data").cloned().unwrap_or_default()
+        };
+        let Some(id) = data.get("conduct") else {
+            eprintln!(
+                "{} No code of conduct configured to reload.",
+                "[ERROR]".red()
+            );
+            println!(
+                "{} Run `{}` to choose one",
+                "[FIX]".green(),
+                "cu conduct set".grey()
+            );
+            process::exit(1);
+        };
+        if let Err(e) = Self::write_conduct(id, false) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            println!(
+                "{} Add the values to curator.json or run `{}`",
+                "[FIX]".green(),
+                "cu conduct set".grey()
+            );
+            process::exit(1);
+        }
+    }
+
+    /// Writes `CODE_OF_CONDUCT.md` for `id` with the version and language stored in config,
+    /// prompting for missing placeholders when `interactive`
+    ///
+    /// The choice is saved to `curator.json` only once the file has been written.
+    pub fn write_conduct(id: &str, interactive: bool) -> Result<(), String> {
+        let data = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard.get("data").cloned().unwrap_or_default()
+        };
+        let registry = ConductRegistry::load();
+        let conduct = registry
+            .get(id)
+            .ok_or_else(|| format!("Code of conduct '{id}' not found"))?;
+        let stored = data.get("conduct").is_some_and(|stored| stored == id);
+        let mut version = data.get("conduct_version").filter(|_| stored).cloned();
+        if stored && version.is_none() {
+            // Configs from before versions were recorded always got the default variant
+            let assumed = match conduct.id.as_str() {
+                "contributor-covenant" => LEGACY_COVENANT_VERSION,
+                _ => conduct.latest_version(),
+            };
+            println!(
+                "{} No conduct version recorded, using {}.",
+                "[INFO]".yellow(),
+                assumed
+            );
+            version = Some(assumed.to_string());
+        }
+        let language = data.get("conduct_language").filter(|_| stored).cloned();
+        let variant = conduct.variant(version.as_deref(), language.as_deref())?;
+
+        let text = Self::fetch_conduct(conduct, variant, false).map_err(|e| e.to_string())?;
+        let text = if interactive {
+            LicenseManager::modify_license(text)
+        } else {
+            LicenseManager::modify_license_stored(text)?
+        };
+        std::fs::write("CODE_OF_CONDUCT.md", text)
+            .map_err(|e| format!("Failed to write CODE_OF_CONDUCT.md: {e}"))?;
+        {
+            let mut config_guard = CONFIGURATION.lock().unwrap();
+            if let Some(data) = config_guard.get_mut("data") {
+                data.insert("conduct".to_string(), conduct.id.clone());
+                data.insert("conduct_version".to_string(), variant.version.clone());
+                data.insert("conduct_language".to_string(), variant.language.clone());
+            }
+        }
+        ConfigManager::save_config();
+        println!(
+            "{} Saved {} {} ({}) to CODE_OF_CONDUCT.md",
+            "[SUCCESS]".green(),
+            conduct.id,
+            variant.version,
+            variant.language
+        );
+        Ok(())
+    }
+
+    /// Identifies which code, version and language an existing code of conduct is based on
+    pub fn detect_conduct(path: Option<String>) {
+        let path = match path {
+            Some(path) => std::path::PathBuf::from(path),
+            None => match [
+                "CODE_OF_CONDUCT.md",
+                ".github/CODE_OF_CONDUCT.md",
+                "docs/CODE_OF_CONDUCT.md",
+            ]
+            .iter()
+            .map(std::path::PathBuf::from)
+            .find(|path| path.is_file())
+            {
+                Some(path) => path,
+                None => {
+                    eprintln!("{} No CODE_OF_CONDUCT.md found.", "[ERROR]".red());
+                    println!(
+                        "{} Run `{}` to create one",
+                        "[FIX]".green(),
+                        "cu conduct set".grey()
+                    );
+                    process::exit(1);
+                }
+            },
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
                 eprintln!(
-                    "{} Failed to remove CODE_OF_CONDUCT.md file: {}",
+                    "{} Failed to read {}: {}",
                     "[ERROR]".red(),
+                    path.display(),
                     e
                 );
                 process::exit(1);
             }
+        };
+
+        println!("{} {}", "Code of Conduct Detection".bold(), path.display());
+        let registry = ConductRegistry::load();
+        let detected = Self::identify(&registry, &text);
+        match detected {
+            Some((conduct, variant, score)) => {
+                println!(
+                    "Detected: {} ({}) version {}, {} - {:.0}% match",
+                    conduct.title.clone().green(),
+                    conduct.id,
+                    variant.version,
+                    variant.language,
+                    score * 100.0
+                );
+                let newer: Vec<&str> = conduct
+                    .versions()
+                    .into_iter()
+                    .filter(|version| {
+                        Tools::compare_versions(version, &variant.version).is_gt()
+                            && conduct
+                                .languages(version)
+                                .contains(&variant.language.as_str())
+                    })
+                    .collect();
+                match newer.first() {
+                    Some(latest) => {
+                        println!(
+                            "{} A newer version is available: {}",
+                            "[UPDATE]".yellow(),
+                            latest
+                        );
+                        println!(
+                            "{} Run `{}` to update",
+                            "[FIX]".green(),
+                            format!(
+                                "cu conduct set {} --version {} --language {}",
+                                conduct.id, latest, variant.language
+                            )
+                            .grey()
+                        );
+                    }
+                    None => println!("{} This is the newest version.", "[SUCCESS]".green()),
+                }
+            }
+            None => println!(
+                "{} The file does not match any known code of conduct.",
+                "[INFO]".yellow()
+            ),
+        }
+
+        let unfilled = Self::unfilled_placeholders(&text);
+        if unfilled.is_empty() {
+            println!("{} No unfilled placeholders.", "[SUCCESS]".green());
         } else {
-            println!("{} CODE_OF_CONDUCT.md file removed.", "[SUCCESS]".green());
+            println!("{} Unfilled placeholders:", "[WARNING]".yellow());
+            for placeholder in unfilled {
+                println!("  {}", placeholder.red());
+            }
+            println!(
+                "{} Run `{}` or `{}` to fill them",
+                "[FIX]".green(),
+                "cu conduct reload".grey(),
+                "cu conduct set".grey()
+            );
         }
     }
 
-    /// Preview the code of conduct
-    pub fn preview_conduct() {
-        println!("Would you prefer the:");
-        println!("  1. Contributor Covenant Code of Conduct - Suitable for projects of all sizes");
-        println!("  2. Django Code of Conduct - Suitable for large communities and events");
-        println!("  3. Citizen Code of Conduct - Suitable for most projects");
-        let conduct = loop {
-            let choice: String = Input::new()
-                .with_prompt("Enter the number of your choice (1, 2 or 3)")
-                .interact_text()
-                .unwrap();
+    /// Finds the closest matching code variant and its similarity score
+    pub fn identify<'a>(
+        registry: &'a ConductRegistry,
+        text: &str,
+    ) -> Option<(&'a ConductInfo, &'a ConductVariant, f64)> {
+        let mut best: Option<(&ConductInfo, &ConductVariant, f64)> = None;
+        for conduct in registry.all() {
+            for variant in &conduct.variants {
+                let template = match variant.bundled {
+                    Some(bundled) => bundled.to_string(),
+                    None if !variant.source.starts_with("http") => {
+                        match std::fs::read_to_string(&variant.source) {
+                            Ok(template) => template,
+                            Err(_) => continue,
+                        }
+                    }
+                    None => continue,
+                };
+                let score = Tools::text_similarity(&template, text);
+                if best.is_none_or(|(_, _, best_score)| score > best_score) {
+                    best = Some((conduct, variant, score));
+                }
+            }
+        }
+        best.filter(|(_, _, score)| *score >= 0.5)
+    }
 
-            match choice.trim() {
-                "1" => break "Contributor Covenant",
-                "2" => break "Django",
-                "3" => break "Citizen",
-                _ => {
-                    println!("{}", "Please enter 1, 2 or 3".red());
-                    continue;
+    /// Returns template placeholders such as `<email>` or `[INSERT CONTACT METHOD]` left in a text
+    pub fn unfilled_placeholders(text: &str) -> Vec<String> {
+        let mut found: Vec<String> = Vec::new();
+        for (open, close) in [('<', '>'), ('[', ']')] {
+            let mut rest = text;
+            while let Some(start) = rest.find(open) {
+                let after = &rest[start + open.len_utf8()..];
+                let Some(end) = after.find(close) else {
+                    break;
+                };
+                let inner = &after[..end];
+                let is_placeholder = match open {
+                    '<' => {
+                        !inner.is_empty()
+                            && inner.len() <= 80
+                            && !inner.starts_with("http")
+                            && !inner.starts_with("mailto:")
+                            && !inner.starts_with('/')
+                            && !inner.starts_with('!')
+                            && !inner.contains(' ')
+                            && !inner.contains('=')
+                            && inner.contains(['-', '('])
+                            || ["email", "year", "faq", "copyright holders"].contains(&inner)
+                    }
+                    _ => inner.starts_with("INSERT "),
+                };
+                let placeholder = format!("{open}{inner}{close}");
+                if is_placeholder && !found.contains(&placeholder) {
+                    found.push(placeholder);
                 }
+                rest = &after[end + close.len_utf8()..];
             }
+        }
+        found
+    }
+
+    /// Shows which code of conduct, version and language the project uses
+    pub fn conduct_status() {
+        ConfigManager::load_config();
+        let data = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard.get("data").cloned().unwrap_or_default()
         };
-        let conduct_formatted = conduct.to_lowercase().replace(" ", "-");
-        let url = format!(
-            "https://raw.githubusercontent.com/Saturnyx/curator/refs/heads/main/templates/conduct/{conduct_formatted}.md"
+        let Some(id) = data.get("conduct") else {
+            println!("{} No code of conduct configured.", "[INFO]".yellow());
+            println!(
+                "{} Run `{}` to choose one",
+                "[FIX]".green(),
+                "cu conduct set".grey()
+            );
+            return;
+        };
+        let registry = ConductRegistry::load();
+        let title = registry
+            .get(id)
+            .map(|conduct| conduct.title.clone())
+            .unwrap_or_else(|| id.clone());
+        println!("{}", "Code of Conduct".bold());
+        println!("Code: {} ({})", title.green(), id);
+        println!(
+            "Version: {}",
+            data.get("conduct_version")
+                .cloned()
+                .unwrap_or_else(|| "not recorded".to_string())
+        );
+        println!(
+            "Language: {}",
+            data.get("conduct_language")
+                .cloned()
+                .unwrap_or_else(|| "not recorded".to_string())
+        );
+        println!(
+            "File: {}",
+            if std::path::Path::new("CODE_OF_CONDUCT.md").exists() {
+                "CODE_OF_CONDUCT.md".green()
+            } else {
+                "CODE_OF_CONDUCT.md (missing)".red()
+            }
         );
+    }
 
-        let response = match HTTP_CLIENT.get(&url).send() {
-            Ok(resp) => resp,
+    /// Copies a code of conduct from a path or URL into the template store and returns its id
+    fn add_custom_conduct(source: &str, name: Option<String>, org: bool) -> String {
+        let is_url = source.starts_with("http://") || source.starts_with("https://");
+        let text = if is_url {
+            let response = HTTP_CLIENT
+                .get(source)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.text());
+            match response {
+                Ok(text) => text,
+                Err(e) => {
+                    eprintln!("{} Failed to download {}: {}", "[ERROR]".red(), source, e);
+                    process::exit(1);
+                }
+            }
+        } else {
+            match std::fs::read_to_string(source) {
+                Ok(text) => text,
+                Err(e) => {
+                    eprintln!("{} Failed to read {}: {}", "[ERROR]".red(), source, e);
+                    process::exit(1);
+                }
+            }
+        };
+        let id = name
+            .unwrap_or_else(|| Self::source_stem(source, is_url))
+            .trim()
+            .to_lowercase();
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        {
+            eprintln!(
+                "{} '{}' is not a valid code of conduct id. Use letters, digits, '-', '_' and '.'.",
+                "[ERROR]".red(),
+                id
+            );
+            process::exit(1);
+        }
+        if ConductRegistry::is_builtin(&id) {
+            eprintln!(
+                "{} '{}' is a built-in code of conduct. Give the custom code another id.",
+                "[ERROR]".red(),
+                id
+            );
+            process::exit(1);
+        }
+        let scope = if org {
+            StoreScope::Org
+        } else {
+            StoreScope::User
+        };
+        match TemplateStore::add("conduct", &format!("{id}.md"), &text, scope) {
+            Ok(path) => println!(
+                "{} Stored custom code of conduct '{}' ({})",
+                "[SUCCESS]".green(),
+                id,
+                path.display()
+            ),
             Err(e) => {
-                eprintln!("{} Failed to fetch conduct: {}", "[ERROR]".red(), e);
+                eprintln!("{} Failed to store code of conduct: {}", "[ERROR]".red(), e);
                 process::exit(1);
             }
+        }
+        id
+    }
+
+    /// Derives a custom conduct id from a file name, such as `team` for `.../team.md`
+    ///
+    /// URLs such as `.../team.md?raw=true#top` keep their query and fragment out of the id.
+    fn source_stem(source: &str, is_url: bool) -> String {
+        let path = if is_url {
+            source.split(['?', '#']).next().unwrap_or(source)
+        } else {
+            source
         };
+        path.trim_end_matches('/')
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(".md")
+            .to_string()
+    }
 
-        if !response.status().is_success() {
+    /// Picks a variant of the code, or exits when it does not exist
+    fn select_variant(
+        conduct: &ConductInfo,
+        version: Option<String>,
+        language: Option<String>,
+    ) -> &ConductVariant {
+        match conduct.variant(version.as_deref(), language.as_deref()) {
+            Ok(variant) => variant,
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Removes the code of conduct
+    pub fn remove_conduct() {
+        if let Err(e) = std::fs::remove_file("CODE_OF_CONDUCT.md") {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                println!(
+                    "{} CODE_OF_CONDUCT.md file does not exist.",
+                    "[INFO]".yellow()
+                );
+            } else {
+                eprintln!(
+                    "{} Failed to remove CODE_OF_CONDUCT.md file: {}",
+                    "[ERROR]".red(),
+                    e
+                );
+                process::exit(1);
+            }
+        } else {
+            println!("{} CODE_OF_CONDUCT.md file removed.", "[SUCCESS]".green());
+        }
+    }
+
+    /// Lists all available codes of conduct
+    pub fn list_conducts() {
+        println!("{}", "Code of Conduct List".bold());
+        for conduct in ConductRegistry::load().all() {
             println!(
-                "Failed to download conduct '{}': HTTP {} - {}",
-                conduct,
-                response.status().as_u16(),
-                response
-                    .status()
-                    .canonical_reason()
-                    .unwrap_or("Unknown error")
+                "{} - {}: {}",
+                conduct.id.clone().cyan(),
+                conduct.title,
+                conduct.description
             );
-            process::exit(1);
-        } else {
-            println!("{} Loaded Code of Conduct", "[SUCCESS]".green());
+            let versions: Vec<String> = conduct
+                .versions()
+                .into_iter()
+                .map(|version| format!("{} ({})", version, conduct.languages(version).join(", ")))
+                .collect();
+            println!("    versions: {}", versions.join(", "));
         }
-        let response_text = match response.text() {
+    }
+
+    /// Preview the code of conduct
+    pub fn preview_conduct(
+        id: Option<String>,
+        version: Option<String>,
+        language: Option<String>,
+        latest: bool,
+    ) {
+        let registry = ConductRegistry::load();
+        let conduct = registry.select(id);
+        let variant = Self::select_variant(conduct, version, language);
+        let response_text = match Self::fetch_conduct(conduct, variant, latest) {
             Ok(text) => text,
             Err(e) => {
-                eprintln!("{} Failed to read license text: {}", "[ERROR]".red(), e);
+                eprintln!("{} Failed to fetch conduct: {}", "[ERROR]".red(), e);
                 process::exit(1);
             }
         };
+        println!("{} Loaded Code of Conduct", "[SUCCESS]".green());
         println!("{}", "Preview -----".bold());
         println!("{response_text}");
         print!("{}", "End -----".bold())
     }
 
-    /// Download the code of conduct
-    fn download_conduct(conduct: String) -> Result<(), Box<dyn std::error::Error>> {
-        let url = format!(
-            "https://raw.githubusercontent.com/Saturnyx/curator/refs/heads/main/templates/conduct/{conduct}.md"
-        );
-        let response = HTTP_CLIENT.get(&url).send()?;
+    /// Returns the template text of a code of conduct
+    ///
+    /// Uses the bundled copy unless `latest` asks for the one published upstream.
+    fn fetch_conduct(
+        conduct: &ConductInfo,
+        variant: &ConductVariant,
+        latest: bool,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if let (Some(bundled), false) = (variant.bundled, latest) {
+            return Ok(bundled.to_string());
+        }
+        if !variant.source.starts_with("http") {
+            return Ok(std::fs::read_to_string(&variant.source)?);
+        }
+        let response = HTTP_CLIENT.get(&variant.source).send()?;
 
         if !response.status().is_success() {
             return Err(format!(
                 "Failed to download '{}': HTTP {} - {}",
-                conduct,
+                conduct.id,
                 response.status().as_u16(),
                 response
                     .status()
@@ -181,15 +891,87 @@ impl ConductManager {
                     .unwrap_or("Unknown error")
             )
             .into());
-        } else {
-            println!("{} Loaded Code of Conduct", "SUCCESS".green());
         }
 
-        let mut response_text = response.text()?;
+        Ok(response.text()?)
+    }
+
+    /// Download the code of conduct
+    fn download_conduct(
+        conduct: &ConductInfo,
+        variant: &ConductVariant,
+        latest: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut response_text = Self::fetch_conduct(conduct, variant, latest)?;
+        println!("{} Loaded Code of Conduct", "[SUCCESS]".green());
         response_text = LicenseManager::modify_license(response_text);
         let filename = "CODE_OF_CONDUCT.md".to_string();
         std::fs::write(&filename, response_text)?;
-        println!("{}", format!("Downloaded {conduct} to {filename}").green());
+        println!("{}", format!("Saved {} to {filename}", conduct.id).green());
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identified(text: &str) -> Option<(String, String, String)> {
+        let registry = ConductRegistry::builtin();
+        ConductManager::identify(&registry, text).map(|(conduct, variant, _)| {
+            (
+                conduct.id.clone(),
+                variant.version.clone(),
+                variant.language.clone(),
+            )
+        })
+    }
+
+    #[test]
+    fn identify_finds_version_and_language() {
+        let text = include_str!("../templates/conduct/contributor-covenant-1.4.md")
+            .replace("<email>", "conduct@example.com");
+        let (id, version, language) = identified(&text).unwrap();
+        assert_eq!(
+            (id.as_str(), version.as_str(), language.as_str()),
+            ("contributor-covenant", "1.4", "en")
+        );
+
+        let text = include_str!("../templates/conduct/contributor-covenant-2.1-de.md");
+        let (_, version, language) = identified(text).unwrap();
+        assert_eq!((version.as_str(), language.as_str()), ("2.1", "de"));
+
+        let text = include_str!("../templates/conduct/django.md");
+        assert_eq!(identified(text).unwrap().0, "django");
+    }
+
+    #[test]
+    fn identify_ignores_unrelated_text() {
+        assert!(identified("# Notes\n\nNothing to see here.\n").is_none());
+    }
+
+    #[test]
+    fn unfilled_placeholders_skips_links_and_html() {
+        let text = "Contact <email> or [INSERT CONTACT METHOD] by <span-name>.\nSee <https://example.com> and <!-- comment --> or [the docs](docs.md).\n";
+        assert_eq!(
+            ConductManager::unfilled_placeholders(text),
+            ["<email>", "<span-name>", "[INSERT CONTACT METHOD]"]
+        );
+    }
+
+    #[test]
+    fn source_stem_ignores_url_query_and_fragment() {
+        assert_eq!(
+            ConductManager::source_stem("https://example.com/team-conduct.md?raw=true#top", true),
+            "team-conduct"
+        );
+        assert_eq!(
+            ConductManager::source_stem("conducts/acme-v2.md", false),
+            "acme-v2"
+        );
+        assert_eq!(
+            ConductManager::source_stem("C:\\docs\\ours.md", false),
+            "ours"
+        );
+    }
+}
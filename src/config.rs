@@ -189,13 +189,14 @@ impl ConfigManager {
             .interact_text()
             .unwrap();
         let project_license: String;
-        let licenses = match LicenseManager::get_licenses() {
+        let mut licenses = match LicenseManager::get_licenses() {
             Ok(licenses) => licenses,
             Err(e) => {
                 eprintln!("{} Could not get Licenses: {}", "[ERROR]".red(), e);
                 process::exit(1);
             }
         };
+        licenses.extend(LicenseManager::custom_licenses());
         let licenses_lower: Vec<String> = licenses.iter().map(|l| l.to_lowercase()).collect();
         loop {
             let input_license: String = Input::new()
@@ -267,8 +268,8 @@ impl ConfigManager {
             let lines = fs::read_to_string(gitignore_path)
                 .unwrap_or_default()
                 .lines()
-                .map(|l| l.trim().to_string())
-                .filter(|l| l != filename && !l.is_empty())
+                .filter(|l| l.trim() != filename)
+                .map(str::to_string)
                 .collect::<Vec<_>>();
             if let Err(e) = fs::write(
                 gitignore_path,
@@ -0,0 +1,184 @@
+use crossterm::style::Stylize;
+use std::path::Path;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::ecosystem::Ecosystem;
+use crate::git::Git;
+use crate::standards::StandardsReport;
+use crate::tools::Tools;
+
+pub struct ContributingManager;
+
+impl ContributingManager {
+    /// Creates `CONTRIBUTING.md` for the detected or given ecosystems
+    ///
+    /// An existing guide, in any letter case or forge location, is only replaced with `force`.
+    pub fn set_contributing(ecosystems: Vec<String>, force: bool) {
+        if let Some(path) = Self::existing() {
+            if !force {
+                eprintln!("{} {} already exists.", "[ERROR]".red(), path);
+                println!(
+                    "{} Run `{}` to replace it",
+                    "[FIX]".green(),
+                    "cu contributing set --force".grey()
+                );
+                process::exit(1);
+            }
+        }
+        let ecosystems = if ecosystems.is_empty() {
+            None
+        } else {
+            Some(Self::parse_ecosystems(&ecosystems))
+        };
+        if let Err(e) = Self::write(ecosystems, true) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+
+    /// Generates `CONTRIBUTING.md`, prompting for missing values when `interactive`
+    pub fn generate(interactive: bool) -> Result<(), String> {
+        if let Some(path) = Self::existing() {
+            return Err(format!("{path} already exists."));
+        }
+        Self::write(None, interactive)
+    }
+
+    /// Finds an existing contributing guide, such as `CONTRIBUTING.MD` or `docs/CONTRIBUTING.md`
+    fn existing() -> Option<String> {
+        StandardsReport::check_standard(Path::new("."), "contributing").and_then(|check| check.path)
+    }
+
+    /// Renders the template and writes the guide, replacing an existing one in place
+    fn write(ecosystems: Option<Vec<Ecosystem>>, interactive: bool) -> Result<(), String> {
+        ConfigManager::load_config();
+        let root = Path::new(".");
+        let path = Self::existing().unwrap_or_else(|| "CONTRIBUTING.md".to_string());
+        let ecosystems = ecosystems.unwrap_or_else(|| Ecosystem::detect(root));
+        if ecosystems.is_empty() {
+            println!(
+                "{} No Cargo.toml, package.json, pyproject.toml or go.mod found; writing generic instructions.",
+                "[INFO]".yellow()
+            );
+        } else {
+            let names: Vec<&str> = ecosystems.iter().map(|e| e.name()).collect();
+            println!("Detected ecosystem: {}", names.join(", ").green());
+        }
+
+        let template = Self::render(root, &ecosystems);
+        Tools::write_template(&path, &template, interactive)?;
+        println!("{} Created {}", "[SUCCESS]".green(), path);
+        Ok(())
+    }
+
+    /// Fills the sections of the template that depend on the project
+    fn render(root: &Path, ecosystems: &[Ecosystem]) -> String {
+        let data = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard.get("data").cloned().unwrap_or_default()
+        };
+        let report = StandardsReport::evaluate(root);
+        let found = |id: &str| {
+            report
+                .checks
+                .iter()
+                .find(|check| check.id == id)
+                .and_then(|check| check.path.clone())
+        };
+
+        let conduct = match found("code-of-conduct") {
+            Some(path) => Some(path),
+            None if data.contains_key("conduct") => Some("CODE_OF_CONDUCT.md".to_string()),
+            None => None,
+        }
+        .map(|path| {
+            format!(
+                "\n## Code of Conduct\n\nThis project follows a [Code of Conduct]({path}). By participating,\nyou are expected to uphold it.\n"
+            )
+        })
+        .unwrap_or_default();
+
+        let license_path = found("license").unwrap_or_else(|| "LICENSE".to_string());
+        let license = match data.get("license") {
+            Some(_) => format!(
+                "\n## License\n\nBy contributing, you agree that your contributions will be licensed under the\n<license> license, as described in the [LICENSE]({license_path}) file.\n"
+            ),
+            None if found("license").is_some() => format!(
+                "\n## License\n\nBy contributing, you agree that your contributions will be licensed under the\nproject's license, as described in the [LICENSE]({license_path}) file.\n"
+            ),
+            None => String::new(),
+        };
+
+        let branch = Git::default_branch().unwrap_or_else(|| "main".to_string());
+
+        include_str!("../templates/community/contributing.md")
+            .replace(
+                "{{development}}",
+                &Self::development_section(root, ecosystems),
+            )
+            .replace("{{branch}}", &branch)
+            .replace("{{conduct}}", &conduct)
+            .replace("{{license}}", &license)
+    }
+
+    /// Describes how to set up, build, test and lint each ecosystem
+    fn development_section(root: &Path, ecosystems: &[Ecosystem]) -> String {
+        let mut section = String::from("## Development\n\n");
+        if ecosystems.is_empty() {
+            section
+                .push_str("See the [README](README.md) for how to build and test the project.\n");
+            return section;
+        }
+        for ecosystem in ecosystems {
+            let commands = ecosystem.commands(root);
+            if ecosystems.len() > 1 {
+                section.push_str(&format!("### {}\n\n", ecosystem.name()));
+            } else {
+                section.push_str(&format!("This is a {} project.\n\n", ecosystem.name()));
+            }
+            section.push_str("```bash\n");
+            let steps = [
+                ("Install dependencies", &commands.setup),
+                ("Build", &commands.build),
+                ("Run the tests", &commands.test),
+                ("Lint", &commands.lint),
+                ("Format the code", &commands.format),
+            ];
+            let mut first = true;
+            for (label, command) in steps {
+                if let Some(command) = command {
+                    if !first {
+                        section.push('\n');
+                    }
+                    section.push_str(&format!("# {label}\n{command}\n"));
+                    first = false;
+                }
+            }
+            section.push_str("```\n\n");
+        }
+        section.push_str(
+            "Please run the tests, linter and formatter before opening a pull request.\n",
+        );
+        section
+    }
+
+    /// Parses ecosystem ids, exiting on unknown ones
+    fn parse_ecosystems(ids: &[String]) -> Vec<Ecosystem> {
+        ids.iter()
+            .map(|id| match Ecosystem::from_id(id) {
+                Some(ecosystem) => ecosystem,
+                None => {
+                    let known: Vec<&str> = Ecosystem::ALL.iter().map(|e| e.id()).collect();
+                    eprintln!(
+                        "{} Unknown ecosystem '{}'. Use one of: {}",
+                        "[ERROR]".red(),
+                        id,
+                        known.join(", ")
+                    );
+                    process::exit(1);
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,287 @@
+use crossterm::style::Stylize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::tools::Tools;
+
+/// Section of `curator.json` with the schedule and grouping of update pull requests
+const SETTINGS_SECTION: &str = "deps-bot";
+
+/// A package ecosystem the update bots can watch
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum PackageEcosystem {
+    Cargo,
+    Npm,
+    Pip,
+    GithubActions,
+    Docker,
+}
+
+/// How often and how update pull requests are opened
+struct BotSettings {
+    interval: String,
+    day: String,
+    limit: u32,
+    group: bool,
+}
+
+pub struct DepsBotManager;
+
+impl PackageEcosystem {
+    fn dependabot(&self) -> &'static str {
+        match self {
+            PackageEcosystem::Cargo => "cargo",
+            PackageEcosystem::Npm => "npm",
+            PackageEcosystem::Pip => "pip",
+            PackageEcosystem::GithubActions => "github-actions",
+            PackageEcosystem::Docker => "docker",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PackageEcosystem::Cargo => "Cargo",
+            PackageEcosystem::Npm => "npm",
+            PackageEcosystem::Pip => "pip",
+            PackageEcosystem::GithubActions => "GitHub Actions",
+            PackageEcosystem::Docker => "Docker",
+        }
+    }
+
+    /// Returns the ecosystem a project file belongs to
+    fn of(file: &Path) -> Option<Self> {
+        let name = file.file_name()?.to_string_lossy();
+        let in_workflows = file
+            .parent()
+            .is_some_and(|dir| dir.ends_with(".github/workflows"));
+        if in_workflows && (name.ends_with(".yml") || name.ends_with(".yaml")) {
+            return Some(PackageEcosystem::GithubActions);
+        }
+        match name.as_ref() {
+            "Cargo.toml" => Some(PackageEcosystem::Cargo),
+            "package.json" => Some(PackageEcosystem::Npm),
+            "pyproject.toml" | "setup.py" | "setup.cfg" | "Pipfile" => Some(PackageEcosystem::Pip),
+            name if name.starts_with("requirements") && name.ends_with(".txt") => {
+                Some(PackageEcosystem::Pip)
+            }
+            name if name == "Dockerfile"
+                || name.starts_with("Dockerfile.")
+                || name.ends_with(".Dockerfile") =>
+            {
+                Some(PackageEcosystem::Docker)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl DepsBotManager {
+    /// Writes `.github/dependabot.yml` or `renovate.json` for the ecosystems in the project
+    pub fn set_bot(bot: String, force: bool) {
+        ConfigManager::load_config();
+        let path = match bot.to_lowercase().as_str() {
+            "dependabot" => ".github/dependabot.yml",
+            "renovate" => "renovate.json",
+            _ => {
+                eprintln!(
+                    "{} Unknown bot '{}'. Use dependabot or renovate.",
+                    "[ERROR]".red(),
+                    bot
+                );
+                process::exit(1);
+            }
+        };
+        if Path::new(path).exists() && !force {
+            eprintln!("{} {} already exists.", "[ERROR]".red(), path);
+            println!(
+                "{} Run `{}` to replace it",
+                "[FIX]".green(),
+                format!("cu deps-bot set {} --force", bot.to_lowercase()).grey()
+            );
+            process::exit(1);
+        }
+
+        let found = Self::detect();
+        if found.is_empty() {
+            eprintln!(
+                "{} No Cargo, npm, pip, GitHub Actions or Docker files found.",
+                "[ERROR]".red()
+            );
+            process::exit(1);
+        }
+        let names: Vec<&str> = found.keys().map(PackageEcosystem::name).collect();
+        println!("Detected: {}", names.join(", ").green());
+
+        let settings = Self::settings();
+        let text = if path == "renovate.json" {
+            Self::renovate(&found, &settings)
+        } else {
+            Self::dependabot(&found, &settings)
+        };
+        match Tools::write_file(path, &text) {
+            Ok(()) => println!("{} Created {}", "[SUCCESS]".green(), path),
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Maps each ecosystem to the directories holding its manifests, like `/` or `/web`
+    fn detect() -> BTreeMap<PackageEcosystem, BTreeSet<String>> {
+        let mut found: BTreeMap<PackageEcosystem, BTreeSet<String>> = BTreeMap::new();
+        for file in Tools::project_files(Path::new(".")) {
+            let Some(ecosystem) = PackageEcosystem::of(&file) else {
+                continue;
+            };
+            let dir = if ecosystem == PackageEcosystem::GithubActions {
+                "/".to_string()
+            } else {
+                let parent = file.parent().map(|dir| dir.to_string_lossy().to_string());
+                format!("/{}", parent.unwrap_or_default())
+            };
+            found.entry(ecosystem).or_default().insert(dir);
+        }
+        // The file walk outside a git work tree skips dot-directories, so look for workflows directly
+        let workflows = std::fs::read_dir(Path::new(".github/workflows"))
+            .map(|entries| {
+                entries.flatten().any(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    entry.path().is_file() && (name.ends_with(".yml") || name.ends_with(".yaml"))
+                })
+            })
+            .unwrap_or(false);
+        if workflows {
+            found
+                .entry(PackageEcosystem::GithubActions)
+                .or_default()
+                .insert("/".to_string());
+        }
+        found
+    }
+
+    /// Reads `schedule`, `day`, `limit` and `group` from the `deps-bot` section of `curator.json`
+    fn settings() -> BotSettings {
+        let config_guard = CONFIGURATION.lock().unwrap();
+        let section = config_guard
+            .get(SETTINGS_SECTION)
+            .cloned()
+            .unwrap_or_default();
+        let interval = section
+            .get("schedule")
+            .map(|schedule| schedule.to_lowercase())
+            .filter(|schedule| ["daily", "weekly", "monthly"].contains(&schedule.as_str()))
+            .unwrap_or_else(|| "weekly".to_string());
+        BotSettings {
+            interval,
+            day: section
+                .get("day")
+                .map(|day| day.to_lowercase())
+                .unwrap_or_else(|| "monday".to_string()),
+            limit: section
+                .get("limit")
+                .and_then(|limit| limit.parse().ok())
+                .unwrap_or(5),
+            group: section.get("group").is_none_or(|group| group != "false"),
+        }
+    }
+
+    fn dependabot(
+        found: &BTreeMap<PackageEcosystem, BTreeSet<String>>,
+        settings: &BotSettings,
+    ) -> String {
+        let mut text = String::from("version: 2\nupdates:\n");
+        for (ecosystem, dirs) in found {
+            for dir in dirs {
+                text.push_str(&format!(
+                    "  - package-ecosystem: \"{}\"\n    directory: \"{}\"\n    schedule:\n      interval: \"{}\"\n",
+                    ecosystem.dependabot(),
+                    dir,
+                    settings.interval
+                ));
+                if settings.interval == "weekly" {
+                    text.push_str(&format!("      day: \"{}\"\n", settings.day));
+                }
+                text.push_str(&format!(
+                    "    open-pull-requests-limit: {}\n",
+                    settings.limit
+                ));
+                if settings.group {
+                    text.push_str(&format!(
+                        "    groups:\n      {}:\n        update-types:\n          - \"minor\"\n          - \"patch\"\n",
+                        ecosystem.dependabot()
+                    ));
+                }
+            }
+        }
+        text
+    }
+
+    fn renovate(
+        found: &BTreeMap<PackageEcosystem, BTreeSet<String>>,
+        settings: &BotSettings,
+    ) -> String {
+        let managers: Vec<&str> = found
+            .keys()
+            .flat_map(|ecosystem| match ecosystem {
+                PackageEcosystem::Cargo => vec!["cargo"],
+                PackageEcosystem::Npm => vec!["npm"],
+                PackageEcosystem::Pip => {
+                    vec![
+                        "pip_requirements",
+                        "pip_setup",
+                        "pep621",
+                        "poetry",
+                        "pipenv",
+                    ]
+                }
+                PackageEcosystem::GithubActions => vec!["github-actions"],
+                PackageEcosystem::Docker => vec!["dockerfile"],
+            })
+            .collect();
+        let schedule = match settings.interval.as_str() {
+            "daily" => "before 6am".to_string(),
+            "monthly" => "before 6am on the first day of the month".to_string(),
+            _ => format!("before 6am on {}", settings.day),
+        };
+        let mut config = json!({
+            "$schema": "https://docs.renovatebot.com/renovate-schema.json",
+            "extends": ["config:recommended"],
+            "enabledManagers": managers,
+            "schedule": [schedule],
+            "prConcurrentLimit": settings.limit,
+        });
+        if settings.group {
+            let rules: Vec<Value> = found
+                .keys()
+                .map(|ecosystem| {
+                    let managers: Vec<&str> = managers
+                        .iter()
+                        .copied()
+                        .filter(|manager| match ecosystem {
+                            PackageEcosystem::Pip => {
+                                manager.starts_with("pip")
+                                    || *manager == "pep621"
+                                    || *manager == "poetry"
+                            }
+                            PackageEcosystem::Cargo => *manager == "cargo",
+                            PackageEcosystem::Npm => *manager == "npm",
+                            PackageEcosystem::GithubActions => *manager == "github-actions",
+                            PackageEcosystem::Docker => *manager == "dockerfile",
+                        })
+                        .collect();
+                    json!({
+                        "matchManagers": managers,
+                        "matchUpdateTypes": ["minor", "patch"],
+                        "groupName": format!("{} minor and patch updates", ecosystem.name()),
+                    })
+                })
+                .collect();
+            config["packageRules"] = Value::Array(rules);
+        }
+        serde_json::to_string_pretty(&config).unwrap_or_default() + "\n"
+    }
+}
@@ -0,0 +1,244 @@
+use std::fs;
+use std::path::Path;
+
+/// A package ecosystem curator knows how to build, test and lint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ecosystem {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+/// Commands contributors run while working on a project
+#[derive(Clone, Debug, Default)]
+pub struct EcosystemCommands {
+    pub setup: Option<String>,
+    pub build: Option<String>,
+    pub test: Option<String>,
+    pub lint: Option<String>,
+    pub format: Option<String>,
+}
+
+impl Ecosystem {
+    pub const ALL: [Ecosystem; 4] = [
+        Ecosystem::Rust,
+        Ecosystem::Node,
+        Ecosystem::Python,
+        Ecosystem::Go,
+    ];
+
+    /// Identifier used on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            Ecosystem::Rust => "rust",
+            Ecosystem::Node => "node",
+            Ecosystem::Python => "python",
+            Ecosystem::Go => "go",
+        }
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ecosystem::Rust => "Rust",
+            Ecosystem::Node => "Node.js",
+            Ecosystem::Python => "Python",
+            Ecosystem::Go => "Go",
+        }
+    }
+
+    /// Files whose presence marks a project of this ecosystem
+    pub fn manifests(&self) -> &'static [&'static str] {
+        match self {
+            Ecosystem::Rust => &["Cargo.toml"],
+            Ecosystem::Node => &["package.json"],
+            Ecosystem::Python => &[
+                "pyproject.toml",
+                "setup.py",
+                "setup.cfg",
+                "requirements.txt",
+            ],
+            Ecosystem::Go => &["go.mod"],
+        }
+    }
+
+    /// Parses an ecosystem id such as `rust` or `node`
+    pub fn from_id(id: &str) -> Option<Ecosystem> {
+        let id = id.trim().to_lowercase();
+        let id = match id.as_str() {
+            "cargo" => "rust",
+            "npm" | "nodejs" | "javascript" | "typescript" | "js" | "ts" => "node",
+            "pip" | "py" => "python",
+            "golang" => "go",
+            other => other,
+        };
+        Self::ALL.into_iter().find(|ecosystem| ecosystem.id() == id)
+    }
+
+    /// Returns the ecosystems whose manifests are in `root`
+    pub fn detect(root: &Path) -> Vec<Ecosystem> {
+        Self::ALL
+            .into_iter()
+            .filter(|ecosystem| {
+                ecosystem
+                    .manifests()
+                    .iter()
+                    .any(|manifest| root.join(manifest).is_file())
+            })
+            .collect()
+    }
+
+    /// Reads the package name and description from the manifest in `root`
+    pub fn metadata(&self, root: &Path) -> (Option<String>, Option<String>) {
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap_or_default();
+        match self {
+            Ecosystem::Rust => {
+                let manifest = read("Cargo.toml");
+                (
+                    Self::toml_field(&manifest, &["package"], "name"),
+                    Self::toml_field(&manifest, &["package"], "description"),
+                )
+            }
+            Ecosystem::Node => {
+                let package = serde_json::from_str::<serde_json::Value>(&read("package.json"))
+                    .unwrap_or_default();
+                let field = |key: &str| {
+                    package
+                        .get(key)
+                        .and_then(|value| value.as_str())
+                        .map(str::to_string)
+                };
+                (field("name"), field("description"))
+            }
+            Ecosystem::Python => {
+                let pyproject = read("pyproject.toml");
+                let sections = ["project", "tool.poetry"];
+                (
+                    Self::toml_field(&pyproject, &sections, "name"),
+                    Self::toml_field(&pyproject, &sections, "description"),
+                )
+            }
+            Ecosystem::Go => (
+                read("go.mod")
+                    .lines()
+                    .find_map(|line| line.strip_prefix("module "))
+                    .map(|module| module.trim().to_string()),
+                None,
+            ),
+        }
+    }
+
+    /// Reads a string `key` from one of the `sections` of a TOML manifest
+    fn toml_field(text: &str, sections: &[&str], key: &str) -> Option<String> {
+        let mut section = String::new();
+        for line in text.lines().map(str::trim) {
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                section = name.trim().to_string();
+                continue;
+            }
+            if !sections.contains(&section.as_str()) {
+                continue;
+            }
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            if name.trim() == key {
+                let value = value.trim().trim_matches('"').trim_matches('\'');
+                return (!value.is_empty()).then(|| value.to_string());
+            }
+        }
+        None
+    }
+
+    /// Returns the setup, build, test, lint and format commands for a project in `root`
+    pub fn commands(&self, root: &Path) -> EcosystemCommands {
+        match self {
+            Ecosystem::Rust => EcosystemCommands {
+                setup: None,
+                build: Some("cargo build".to_string()),
+                test: Some("cargo test".to_string()),
+                lint: Some("cargo clippy --all-targets -- -D warnings".to_string()),
+                format: Some("cargo fmt --all".to_string()),
+            },
+            Ecosystem::Node => {
+                let manager = if root.join("pnpm-lock.yaml").is_file() {
+                    "pnpm"
+                } else if root.join("yarn.lock").is_file() {
+                    "yarn"
+                } else if root.join("bun.lockb").is_file() || root.join("bun.lock").is_file() {
+                    "bun"
+                } else {
+                    "npm"
+                };
+                let package = fs::read_to_string(root.join("package.json")).unwrap_or_default();
+                let scripts = serde_json::from_str::<serde_json::Value>(&package)
+                    .ok()
+                    .and_then(|package| package.get("scripts").cloned())
+                    .and_then(|scripts| scripts.as_object().cloned())
+                    .unwrap_or_default();
+                let script = |name: &str| {
+                    scripts.contains_key(name).then(|| match (manager, name) {
+                        ("npm", "test") => "npm test".to_string(),
+                        ("npm", _) => format!("npm run {name}"),
+                        (manager, _) => format!("{manager} {name}"),
+                    })
+                };
+                EcosystemCommands {
+                    setup: Some(format!("{manager} install")),
+                    build: script("build"),
+                    test: script("test"),
+                    lint: script("lint"),
+                    format: script("format"),
+                }
+            }
+            Ecosystem::Python => {
+                let pyproject = fs::read_to_string(root.join("pyproject.toml")).unwrap_or_default();
+                let runner = if pyproject.contains("[tool.poetry") {
+                    "poetry run "
+                } else if root.join("uv.lock").is_file() {
+                    "uv run "
+                } else {
+                    ""
+                };
+                let setup = if pyproject.contains("[tool.poetry") {
+                    "poetry install"
+                } else if root.join("uv.lock").is_file() {
+                    "uv sync"
+                } else if root.join("requirements.txt").is_file() && pyproject.is_empty() {
+                    "pip install -r requirements.txt"
+                } else {
+                    "pip install -e ."
+                };
+                let uses = |tool: &str| pyproject.contains(tool);
+                EcosystemCommands {
+                    setup: Some(setup.to_string()),
+                    build: (!pyproject.is_empty()).then(|| format!("{runner}python -m build")),
+                    test: Some(format!("{runner}pytest")),
+                    lint: Some(if uses("ruff") {
+                        format!("{runner}ruff check .")
+                    } else {
+                        format!("{runner}flake8")
+                    }),
+                    format: Some(if uses("black") {
+                        format!("{runner}black .")
+                    } else if uses("ruff") {
+                        format!("{runner}ruff format .")
+                    } else {
+                        format!("{runner}black .")
+                    }),
+                }
+            }
+            Ecosystem::Go => EcosystemCommands {
+                setup: Some("go mod download".to_string()),
+                build: Some("go build ./...".to_string()),
+                test: Some("go test ./...".to_string()),
+                lint: Some("go vet ./...".to_string()),
+                format: Some("gofmt -w .".to_string()),
+            },
+        }
+    }
+}
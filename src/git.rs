@@ -0,0 +1,101 @@
+use std::process::Command;
+
+pub struct Git;
+
+impl Git {
+    /// Runs git in the current directory and returns its trimmed output on success
+    pub fn run(args: &[&str]) -> Option<String> {
+        let output = Command::new("git").args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Checks if the current directory is inside a git repository
+    pub fn is_repository() -> bool {
+        Self::run(&["rev-parse", "--is-inside-work-tree"]).is_some_and(|out| out == "true")
+    }
+
+    /// Returns the default branch from `origin/HEAD`, falling back to the current branch
+    pub fn default_branch() -> Option<String> {
+        Self::run(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+            .and_then(|head| head.split_once('/').map(|(_, branch)| branch.to_string()))
+            .or_else(|| Self::run(&["symbolic-ref", "--short", "HEAD"]))
+            .filter(|branch| !branch.is_empty())
+    }
+
+    /// Returns all tags of the repository
+    pub fn tags() -> Vec<String> {
+        Self::run(&["tag", "--list"])
+            .map(|out| out.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns release tags such as `v1.2.3` with their parsed version, newest first
+    ///
+    /// Pre-release tags like `v2.0.0-rc.1` are left out.
+    pub fn semver_tags() -> Vec<(String, (u64, u64, u64))> {
+        let mut tags: Vec<(String, (u64, u64, u64))> = Self::tags()
+            .into_iter()
+            .filter_map(|tag| Self::parse_semver(&tag).map(|version| (tag, version)))
+            .collect();
+        tags.sort_by_key(|tag| std::cmp::Reverse(tag.1));
+        tags
+    }
+
+    /// Parses `v1.2.3` or `1.2.3` into its numbers
+    pub fn parse_semver(tag: &str) -> Option<(u64, u64, u64)> {
+        let version = tag.strip_prefix('v').unwrap_or(tag);
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next()?.parse().ok()?;
+        parts.next().is_none().then_some((major, minor, patch))
+    }
+
+    /// Returns tracked and untracked files that are not ignored, relative to the root
+    pub fn files() -> Option<Vec<String>> {
+        Self::run(&["ls-files", "--cached", "--others", "--exclude-standard"])
+            .map(|out| out.lines().map(str::to_string).collect())
+    }
+
+    /// Returns the URL of the `origin` remote
+    pub fn remote_url() -> Option<String> {
+        Self::run(&["remote", "get-url", "origin"]).filter(|url| !url.is_empty())
+    }
+
+    /// Returns the browser URL of the `origin` remote, e.g. `https://github.com/owner/repo`
+    pub fn web_url() -> Option<String> {
+        Self::remote_url().and_then(|url| Self::to_web_url(&url))
+    }
+
+    /// Converts an SSH or HTTPS remote URL into its browser URL
+    pub fn to_web_url(url: &str) -> Option<String> {
+        let url = url.trim().trim_end_matches('/');
+        let url = url.strip_suffix(".git").unwrap_or(url);
+        let (host, path) = if let Some(rest) = url.strip_prefix("git@") {
+            rest.split_once(':')?
+        } else {
+            let rest = url
+                .strip_prefix("https://")
+                .or_else(|| url.strip_prefix("http://"))
+                .or_else(|| url.strip_prefix("ssh://"))?;
+            let rest = rest.split_once('@').map_or(rest, |(_, rest)| rest);
+            rest.split_once('/')?
+        };
+        let host = host.split_once(':').map_or(host, |(host, _)| host);
+        (!path.is_empty()).then(|| format!("https://{host}/{path}"))
+    }
+
+    /// Returns the browser URL of a file on the default branch
+    pub fn file_url(file: &str) -> Option<String> {
+        let web = Self::web_url()?;
+        let branch = Self::default_branch().unwrap_or_else(|| "main".to_string());
+        Some(if web.contains("gitlab") {
+            format!("{web}/-/blob/{branch}/{file}")
+        } else {
+            format!("{web}/blob/{branch}/{file}")
+        })
+    }
+}
@@ -0,0 +1,272 @@
+use crossterm::style::Stylize;
+use std::path::Path;
+use std::process;
+
+use crate::ecosystem::Ecosystem;
+use crate::tools::Tools;
+
+const GITIGNORE: &str = ".gitignore";
+
+/// Bundled templates from github/gitignore as `(id, name, content)`
+const TEMPLATES: &[(&str, &str, &str)] = &[
+    (
+        "rust",
+        "Rust",
+        include_str!("../templates/gitignore/Rust.gitignore"),
+    ),
+    (
+        "node",
+        "Node",
+        include_str!("../templates/gitignore/Node.gitignore"),
+    ),
+    (
+        "python",
+        "Python",
+        include_str!("../templates/gitignore/Python.gitignore"),
+    ),
+    (
+        "go",
+        "Go",
+        include_str!("../templates/gitignore/Go.gitignore"),
+    ),
+    (
+        "macos",
+        "macOS",
+        include_str!("../templates/gitignore/macOS.gitignore"),
+    ),
+    (
+        "windows",
+        "Windows",
+        include_str!("../templates/gitignore/Windows.gitignore"),
+    ),
+    (
+        "vscode",
+        "Visual Studio Code",
+        include_str!("../templates/gitignore/VisualStudioCode.gitignore"),
+    ),
+    (
+        "jetbrains",
+        "JetBrains",
+        include_str!("../templates/gitignore/JetBrains.gitignore"),
+    ),
+];
+
+pub struct GitignoreManager;
+
+impl GitignoreManager {
+    /// Adds or refreshes managed blocks for the given templates, or the detected ecosystems
+    pub fn add_templates(ids: Vec<String>) {
+        let ids = if ids.is_empty() {
+            let detected: Vec<String> = Ecosystem::detect(Path::new("."))
+                .iter()
+                .map(|ecosystem| ecosystem.id().to_string())
+                .collect();
+            if detected.is_empty() {
+                eprintln!(
+                    "{} No ecosystem detected. Name the templates to add, e.g. `{}`",
+                    "[ERROR]".red(),
+                    "cu gitignore add rust macos".grey()
+                );
+                process::exit(1);
+            }
+            println!("Detected ecosystem: {}", detected.join(", ").green());
+            detected
+        } else {
+            ids
+        };
+
+        let mut text = Self::read();
+        for id in &ids {
+            let (id, name, content) = Self::template(id);
+            let existed = Self::find_block(&text, id).is_some();
+            text = Self::put_block(&text, id, content);
+            println!(
+                "{} {} the {} block in .gitignore",
+                "[SUCCESS]".green(),
+                if existed { "Updated" } else { "Added" },
+                name
+            );
+        }
+        Self::write(&text);
+    }
+
+    /// Refreshes every managed block with the bundled template
+    pub fn update_templates() {
+        let mut text = Self::read();
+        let installed = Self::installed(&text);
+        if installed.is_empty() {
+            println!(
+                "{} .gitignore has no blocks managed by curator.",
+                "[INFO]".yellow()
+            );
+            return;
+        }
+        for id in installed {
+            match TEMPLATES.iter().find(|(known, _, _)| *known == id) {
+                Some((id, name, content)) => {
+                    text = Self::put_block(&text, id, content);
+                    println!("{} Updated the {} block", "[SUCCESS]".green(), name);
+                }
+                None => println!(
+                    "{} No bundled template for the '{}' block, leaving it as is.",
+                    "[INFO]".yellow(),
+                    id
+                ),
+            }
+        }
+        Self::write(&text);
+    }
+
+    /// Removes managed blocks, leaving the user's own lines alone
+    pub fn remove_templates(ids: Vec<String>) {
+        let mut text = Self::read();
+        for id in &ids {
+            let id = id.to_lowercase();
+            match Self::find_block(&text, &id) {
+                Some((start, end)) => {
+                    let before = text[..start].trim_end_matches('\n');
+                    let after = text[end..].trim_start_matches('\n');
+                    text = match (before.is_empty(), after.is_empty()) {
+                        (true, _) => after.to_string(),
+                        (false, true) => format!("{before}\n"),
+                        (false, false) => format!("{before}\n\n{after}"),
+                    };
+                    println!("{} Removed the {} block", "[SUCCESS]".green(), id);
+                }
+                None => println!(
+                    "{} .gitignore has no '{}' block managed by curator.",
+                    "[INFO]".yellow(),
+                    id
+                ),
+            }
+        }
+        Self::write(&text);
+    }
+
+    /// Lists the bundled templates, marking the ones in `.gitignore`
+    pub fn list_templates() {
+        let installed = Self::installed(&Self::read());
+        println!("{}", "Available .gitignore templates:".bold());
+        for (id, name, _) in TEMPLATES {
+            if installed.iter().any(|installed| installed == id) {
+                println!("  {} {} {}", "✓".green(), id, format!("({name})").grey());
+            } else {
+                println!("    {} {}", id, format!("({name})").grey());
+            }
+        }
+    }
+
+    fn template(id: &str) -> (&'static str, &'static str, &'static str) {
+        let lower = id.to_lowercase();
+        let lower =
+            Ecosystem::from_id(&lower).map_or(lower, |ecosystem| ecosystem.id().to_string());
+        match TEMPLATES.iter().find(|(known, _, _)| *known == lower) {
+            Some(template) => *template,
+            None => {
+                let known: Vec<&str> = TEMPLATES.iter().map(|(id, _, _)| *id).collect();
+                eprintln!(
+                    "{} Unknown .gitignore template '{}'. Use one of: {}",
+                    "[ERROR]".red(),
+                    id,
+                    known.join(", ")
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    fn start_marker(id: &str) -> String {
+        format!("# >>> curator: {id} >>>")
+    }
+
+    fn end_marker(id: &str) -> String {
+        format!("# <<< curator: {id} <<<")
+    }
+
+    /// Returns the byte range of a managed block including its markers and trailing newline
+    fn find_block(text: &str, id: &str) -> Option<(usize, usize)> {
+        let start = text.find(&Self::start_marker(id))?;
+        let end_marker = Self::end_marker(id);
+        let end = start + text[start..].find(&end_marker)? + end_marker.len();
+        let end = if text[end..].starts_with('\n') {
+            end + 1
+        } else {
+            end
+        };
+        Some((start, end))
+    }
+
+    /// Replaces a managed block in place, or appends it after the existing lines
+    fn put_block(text: &str, id: &str, content: &str) -> String {
+        let block = format!(
+            "{}\n# Do not edit this block, it is replaced by `cu gitignore update`\n{}\n{}\n",
+            Self::start_marker(id),
+            content.trim_end(),
+            Self::end_marker(id)
+        );
+        match Self::find_block(text, id) {
+            Some((start, end)) => format!("{}{}{}", &text[..start], block, &text[end..]),
+            None if text.trim().is_empty() => block,
+            None => format!("{}\n\n{}", text.trim_end_matches('\n'), block),
+        }
+    }
+
+    /// Returns the ids of the managed blocks in file order
+    fn installed(text: &str) -> Vec<String> {
+        text.lines()
+            .filter_map(|line| {
+                line.strip_prefix("# >>> curator: ")
+                    .and_then(|rest| rest.strip_suffix(" >>>"))
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    fn read() -> String {
+        std::fs::read_to_string(GITIGNORE).unwrap_or_default()
+    }
+
+    fn write(text: &str) {
+        if let Err(e) = Tools::write_file(GITIGNORE, text) {
+            eprintln!("{} Failed to update .gitignore: {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_block_appends_after_existing_lines() {
+        let text = GitignoreManager::put_block("target/\n", "rust", "*.rs.bk\n");
+        assert_eq!(
+            text,
+            "target/\n\n# >>> curator: rust >>>\n# Do not edit this block, it is replaced by `cu gitignore update`\n*.rs.bk\n# <<< curator: rust <<<\n"
+        );
+        assert!(GitignoreManager::put_block("\n", "rust", "*.rs.bk")
+            .starts_with("# >>> curator: rust >>>"));
+    }
+
+    #[test]
+    fn put_block_replaces_a_block_in_place() {
+        let text = GitignoreManager::put_block("", "node", "node_modules/");
+        let text = GitignoreManager::put_block(&text, "rust", "target/");
+        let text = format!("{text}.env\n");
+        let updated = GitignoreManager::put_block(&text, "node", "node_modules/\ndist/");
+        assert_eq!(GitignoreManager::installed(&updated), ["node", "rust"]);
+        assert!(updated.contains("node_modules/\ndist/\n# <<< curator: node <<<\n"));
+        assert!(updated.ends_with("# <<< curator: rust <<<\n.env\n"));
+        assert_eq!(updated.matches("# >>> curator: node >>>").count(), 1);
+    }
+
+    #[test]
+    fn find_block_includes_markers_and_trailing_newline() {
+        let text = "a\n# >>> curator: go >>>\nbin/\n# <<< curator: go <<<\nb\n";
+        let (start, end) = GitignoreManager::find_block(text, "go").unwrap();
+        assert_eq!(&text[..start], "a\n");
+        assert_eq!(&text[end..], "b\n");
+        assert!(GitignoreManager::find_block(text, "rust").is_none());
+    }
+}
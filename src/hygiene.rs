@@ -0,0 +1,338 @@
+use crossterm::style::Stylize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process;
+
+use crate::ecosystem::Ecosystem;
+use crate::tools::Tools;
+
+/// EditorConfig sections as `(glob, extensions that trigger it, settings)`
+const EDITORCONFIG_SECTIONS: &[(&str, &[&str], &[&str])] = &[
+    (
+        "*.rs",
+        &["rs"],
+        &[
+            "indent_style = space",
+            "indent_size = 4",
+            "max_line_length = 100",
+        ],
+    ),
+    (
+        "*.py",
+        &["py", "pyi"],
+        &[
+            "indent_style = space",
+            "indent_size = 4",
+            "max_line_length = 88",
+        ],
+    ),
+    ("*.go", &["go"], &["indent_style = tab", "indent_size = 4"]),
+    (
+        "*.{js,jsx,mjs,cjs,ts,tsx,vue,svelte}",
+        &["js", "jsx", "mjs", "cjs", "ts", "tsx", "vue", "svelte"],
+        &["indent_style = space", "indent_size = 2"],
+    ),
+    (
+        "*.{json,yml,yaml}",
+        &["json", "yml", "yaml"],
+        &["indent_style = space", "indent_size = 2"],
+    ),
+    (
+        "*.{html,css,scss,sass,less}",
+        &["html", "css", "scss", "sass", "less"],
+        &["indent_style = space", "indent_size = 2"],
+    ),
+    (
+        "*.toml",
+        &["toml"],
+        &["indent_style = space", "indent_size = 4"],
+    ),
+    (
+        "*.{c,h,cc,cpp,hpp,java,kt,cs}",
+        &["c", "h", "cc", "cpp", "hpp", "java", "kt", "cs"],
+        &["indent_style = space", "indent_size = 4"],
+    ),
+    (
+        "*.{sh,bash,zsh}",
+        &["sh", "bash", "zsh"],
+        &["indent_style = space", "indent_size = 2"],
+    ),
+    (
+        "*.rb",
+        &["rb"],
+        &["indent_style = space", "indent_size = 2"],
+    ),
+    (
+        "*.md",
+        &["md", "markdown"],
+        &["trim_trailing_whitespace = false"],
+    ),
+    (
+        "{Makefile,*.mk}",
+        &["mk", "Makefile"],
+        &["indent_style = tab"],
+    ),
+    (
+        "*.{bat,cmd,ps1}",
+        &["bat", "cmd", "ps1"],
+        &["end_of_line = crlf"],
+    ),
+];
+
+/// Files written by tools, hidden in diffs and left out of language statistics
+const GENERATED: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "poetry.lock",
+    "uv.lock",
+    "Pipfile.lock",
+    "go.sum",
+];
+
+/// Directories holding third-party code
+const VENDORED: &[&str] = &["vendor", "third_party", "third-party", "external"];
+
+/// Extensions git should never try to diff or merge as text
+const BINARY_EXTENSIONS: &[&str] = &[
+    "png",
+    "jpg",
+    "jpeg",
+    "gif",
+    "ico",
+    "webp",
+    "bmp",
+    "tif",
+    "tiff",
+    "psd",
+    "ai",
+    "pdf",
+    "zip",
+    "gz",
+    "tgz",
+    "bz2",
+    "xz",
+    "7z",
+    "rar",
+    "tar",
+    "jar",
+    "war",
+    "whl",
+    "mp3",
+    "mp4",
+    "m4a",
+    "mov",
+    "avi",
+    "mkv",
+    "wav",
+    "flac",
+    "ogg",
+    "webm",
+    "woff",
+    "woff2",
+    "ttf",
+    "otf",
+    "eot",
+    "exe",
+    "dll",
+    "so",
+    "dylib",
+    "bin",
+    "dat",
+    "db",
+    "sqlite",
+    "iso",
+    "dmg",
+    "glb",
+    "fbx",
+    "blend",
+    "h5",
+    "npy",
+    "npz",
+    "pkl",
+    "parquet",
+    "onnx",
+    "pt",
+    "ckpt",
+    "safetensors",
+];
+
+/// Size in MiB from which binary files are tracked with Git LFS by default
+pub const DEFAULT_LFS_THRESHOLD: u64 = 5;
+
+pub struct HygieneManager;
+
+impl HygieneManager {
+    /// Writes `.editorconfig` with indentation and line endings for the languages in the tree
+    pub fn editorconfig(force: bool) {
+        Self::refuse_overwrite(".editorconfig", "cu editorconfig set --force", force);
+        let extensions = Self::extensions(true);
+        let mut text = String::from(
+            "# EditorConfig helps keep coding styles consistent: https://editorconfig.org\nroot = true\n\n[*]\ncharset = utf-8\nend_of_line = lf\ninsert_final_newline = true\ntrim_trailing_whitespace = true\n",
+        );
+        let mut languages = Vec::new();
+        for (glob, triggers, settings) in EDITORCONFIG_SECTIONS {
+            if !triggers
+                .iter()
+                .any(|trigger| extensions.contains_key(*trigger))
+            {
+                continue;
+            }
+            languages.push(*glob);
+            text.push_str(&format!("\n[{glob}]\n"));
+            for setting in *settings {
+                text.push_str(setting);
+                text.push('\n');
+            }
+        }
+        Self::write(".editorconfig", &text);
+        if !languages.is_empty() {
+            println!("Sections: {}", languages.join(", ").green());
+        }
+    }
+
+    /// Writes `.gitattributes` with line ending normalization, linguist markers and LFS patterns
+    ///
+    /// Binary extensions with a file of at least `lfs_threshold` MiB are tracked with Git LFS.
+    pub fn gitattributes(force: bool, lfs_threshold: u64) {
+        Self::refuse_overwrite(".gitattributes", "cu gitattributes set --force", force);
+        let extensions = Self::extensions(false);
+        let files = Tools::project_files(Path::new("."));
+
+        let mut text = String::from("# Normalize line endings\n* text=auto eol=lf\n");
+        for extension in ["bat", "cmd", "ps1"] {
+            if extensions.contains_key(extension) {
+                text.push_str(&format!("*.{extension} text eol=crlf\n"));
+            }
+        }
+
+        let generated: Vec<&str> = GENERATED
+            .iter()
+            .copied()
+            .filter(|name| {
+                files
+                    .iter()
+                    .any(|file| file.file_name().is_some_and(|file| file == *name))
+            })
+            .collect();
+        let vendored: Vec<&str> = VENDORED
+            .iter()
+            .copied()
+            .filter(|dir| Path::new(dir).is_dir())
+            .collect();
+        if !generated.is_empty() || !vendored.is_empty() {
+            text.push_str("\n# Collapsed in diffs and left out of language statistics\n");
+            for name in generated {
+                text.push_str(&format!("{name} linguist-generated=true\n"));
+            }
+            for dir in vendored {
+                text.push_str(&format!("{dir}/** linguist-vendored\n"));
+            }
+        }
+
+        let threshold = lfs_threshold.saturating_mul(1024 * 1024);
+        let mut lfs = BTreeSet::new();
+        let mut binary = BTreeSet::new();
+        for (extension, largest) in &extensions {
+            if !BINARY_EXTENSIONS.contains(&extension.as_str()) {
+                continue;
+            }
+            if *largest >= threshold {
+                lfs.insert(extension.clone());
+            } else {
+                binary.insert(extension.clone());
+            }
+        }
+        if !binary.is_empty() {
+            text.push_str("\n# Binary files\n");
+            for extension in &binary {
+                text.push_str(&format!("*.{extension} binary\n"));
+            }
+        }
+        if !lfs.is_empty() {
+            text.push_str(&format!(
+                "\n# Large binary files (at least {lfs_threshold} MiB) tracked with Git LFS\n"
+            ));
+            for extension in &lfs {
+                text.push_str(&format!(
+                    "*.{extension} filter=lfs diff=lfs merge=lfs -text\n"
+                ));
+            }
+        }
+        Self::write(".gitattributes", &text);
+        if !lfs.is_empty() {
+            let patterns: Vec<String> = lfs
+                .iter()
+                .map(|extension| format!("*.{extension}"))
+                .collect();
+            println!(
+                "{} Run `{}` once, and `{}` to move files already committed",
+                "[INFO]".yellow(),
+                "git lfs install".grey(),
+                format!(
+                    "git lfs migrate import --include=\"{}\"",
+                    patterns.join(",")
+                )
+                .grey()
+            );
+        }
+    }
+
+    /// Maps the extensions in the tree to their largest file size in bytes
+    ///
+    /// Names without an extension, such as `Makefile`, are kept whole. Detected ecosystems add
+    /// their main extension even before any source files exist. With `skip_vendored`, files in
+    /// `VENDORED` directories are left out, since their style is not ours to set.
+    fn extensions(skip_vendored: bool) -> BTreeMap<String, u64> {
+        let mut extensions = BTreeMap::new();
+        for file in Tools::project_files(Path::new(".")) {
+            if skip_vendored
+                && file.components().any(|component| {
+                    VENDORED.contains(&component.as_os_str().to_string_lossy().as_ref())
+                })
+            {
+                continue;
+            }
+            let key = match file.extension() {
+                Some(extension) => extension.to_string_lossy().to_lowercase(),
+                None => file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            };
+            let size = std::fs::metadata(&file).map(|meta| meta.len()).unwrap_or(0);
+            let largest = extensions.entry(key).or_insert(0);
+            *largest = size.max(*largest);
+        }
+        for ecosystem in Ecosystem::detect(Path::new(".")) {
+            let extension = match ecosystem {
+                Ecosystem::Rust => "rs",
+                Ecosystem::Node => "js",
+                Ecosystem::Python => "py",
+                Ecosystem::Go => "go",
+            };
+            extensions.entry(extension.to_string()).or_insert(0);
+        }
+        extensions
+    }
+
+    fn refuse_overwrite(path: &str, fix: &str, force: bool) {
+        if Path::new(path).exists() && !force {
+            eprintln!("{} {} already exists.", "[ERROR]".red(), path);
+            println!("{} Run `{}` to replace it", "[FIX]".green(), fix.grey());
+            process::exit(1);
+        }
+    }
+
+    fn write(path: &str, text: &str) {
+        match Tools::write_file(path, text) {
+            Ok(()) => println!("{} Created {}", "[SUCCESS]".green(), path),
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+}
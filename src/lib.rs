@@ -1,8 +1,26 @@
+pub mod changelog;
+pub mod citation;
+pub mod codeowners;
 pub mod conduct;
 pub mod config;
+pub mod contributing;
+pub mod depsbot;
+pub mod ecosystem;
+pub mod git;
+pub mod gitignore;
+pub mod hygiene;
 pub mod license;
 pub mod misc;
+pub mod placeholder;
+pub mod policy;
 pub mod project;
+pub mod readme;
+pub mod report;
+pub mod security;
+pub mod standards;
+pub mod store;
+pub mod support;
+pub mod templates;
 pub mod tools;
 
 pub use config::ConfigManager;
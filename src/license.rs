@@ -1,5 +1,4 @@
 use crossterm::style::Stylize;
-use dialoguer::Input;
 use once_cell::sync::Lazy;
 use reqwest::blocking::Client;
 use serde::Deserialize;
@@ -7,8 +6,13 @@ use std::process;
 use std::sync::Mutex;
 
 use crate::config::{ConfigManager, CONFIGURATION};
+use crate::placeholder::Placeholders;
+use crate::store::{StoreScope, TemplateStore};
 use crate::tools::Tools;
 
+/// Prefix SPDX reserves for licenses that are not on the license list
+pub const LICENSE_REF_PREFIX: &str = "LicenseRef-";
+
 static LICENSE_CACHE: Lazy<Mutex<Option<Vec<String>>>> = Lazy::new(|| Mutex::new(None));
 static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
     Client::builder()
@@ -50,16 +54,7 @@ impl LicenseManager {
             ConfigManager::load_config();
         }
         license = license.to_lowercase();
-        let license_list = match Self::get_licenses() {
-            Ok(files) => {
-                println!("{} Fetched Licenses", "[SUCCESS]".green());
-                files
-            }
-            Err(e) => {
-                eprintln!("{} Could not fetch licenses: {}", "[ERROR]".red(), e);
-                process::exit(1);
-            }
-        };
+        let license_list = Self::lookup_list(&license);
 
         let license_list_lower: Vec<String> =
             license_list.iter().map(|l| l.to_lowercase()).collect();
@@ -69,8 +64,9 @@ impl LicenseManager {
         if let Some(idx) = license_list_lower.iter().position(|l| l == &search_name) {
             selected_license = license_list[idx].trim_end_matches(".txt").to_string();
             println!(
-                "License '{}' found in SPDX list.",
-                selected_license.clone().green()
+                "License '{}' found in {}.",
+                selected_license.clone().green(),
+                Self::list_name(&license)
             );
             {
                 let mut config_guard = CONFIGURATION.lock().unwrap();
@@ -80,15 +76,16 @@ impl LicenseManager {
             }
             ConfigManager::save_config();
 
-            if let Err(e) = Self::download_license(selected_license) {
+            if let Err(e) = Self::download_license(selected_license, true) {
                 eprintln!("{} {}", "[ERROR]".red(), e);
                 process::exit(1);
             }
         } else {
             println!(
-                "{} License '{}' not found in SPDX list. Please try again.",
+                "{} License '{}' not found in {}. Please try again.",
                 "[ERROR]".red(),
-                license.clone().red()
+                license.clone().red(),
+                Self::list_name(&license)
             );
             let similar_licenses = Tools::fuzzy_search(&license_list_lower, &license);
             if !similar_licenses.is_empty() {
@@ -117,6 +114,14 @@ impl LicenseManager {
 
     /// Reloads the license
     pub fn reload_license() {
+        if let Err(e) = Self::regenerate_license(true) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+
+    /// Reloads the license, using only stored placeholder values unless `interactive`
+    pub fn regenerate_license(interactive: bool) -> Result<(), String> {
         if !ConfigManager::check_config() {
             println!(
                 "{} Project configuration not found or has been misconfigured",
@@ -138,14 +143,11 @@ impl LicenseManager {
                 .and_then(|data| data.get("license"))
                 .cloned()
         };
-        if let Some(license_str) = license {
-            if let Err(e) = Self::download_license(license_str) {
-                eprintln!("{} {}", "[ERROR]".red(), e);
-                process::exit(1);
+        match license {
+            Some(license_str) => {
+                Self::download_license(license_str, interactive).map_err(|e| e.to_string())
             }
-        } else {
-            eprintln!("{} No license configured to edit.", "[ERROR]".red());
-            process::exit(1);
+            None => Err("No license configured to edit.".to_string()),
         }
     }
 
@@ -162,21 +164,23 @@ impl LicenseManager {
         for license in license_list {
             println!("{}", license.trim_end_matches(".txt"));
         }
+        let custom_licenses = Self::scoped_custom_licenses();
+        if !custom_licenses.is_empty() {
+            println!("{}", "Custom Licenses".bold());
+            for (license, scope) in custom_licenses {
+                println!(
+                    "{} {}",
+                    license.trim_end_matches(".txt"),
+                    format!("({})", scope.label()).grey()
+                );
+            }
+        }
     }
 
     /// Preview selected license
     pub fn preview_license(mut license: String) {
         license = license.to_lowercase();
-        let license_list = match Self::get_licenses() {
-            Ok(files) => {
-                println!("{} Fetched Licenses", "[SUCCESS]".green());
-                files
-            }
-            Err(e) => {
-                eprintln!("{} Could not fetch licenses: {}", "[ERROR]".red(), e);
-                process::exit(1);
-            }
-        };
+        let license_list = Self::lookup_list(&license);
 
         let license_list_lower: Vec<String> =
             license_list.iter().map(|l| l.to_lowercase()).collect();
@@ -185,37 +189,14 @@ impl LicenseManager {
         if let Some(idx) = license_list_lower.iter().position(|l| l == &search_name) {
             let selected_license = license_list[idx].trim_end_matches(".txt").to_string();
             println!(
-                "License '{}' found in SPDX list.",
-                selected_license.clone().green()
+                "License '{}' found in {}.",
+                selected_license.clone().green(),
+                Self::list_name(&license)
             );
-            let url = format!(
-        "https://raw.githubusercontent.com/spdx/license-list-data/main/text/{selected_license}.txt"
-    );
-            let response = match HTTP_CLIENT.get(&url).send() {
-                Ok(resp) => resp,
-                Err(e) => {
-                    eprintln!("{} Failed to fetch license: {}", "[ERROR]".red(), e);
-                    process::exit(1);
-                }
-            };
-
-            if !response.status().is_success() {
-                println!(
-                    "Failed to download license '{}': HTTP {} - {}",
-                    license,
-                    response.status().as_u16(),
-                    response
-                        .status()
-                        .canonical_reason()
-                        .unwrap_or("Unknown error")
-                );
-                process::exit(1);
-            }
-
-            let response_text = match response.text() {
+            let response_text = match Self::license_text(&selected_license) {
                 Ok(text) => text,
                 Err(e) => {
-                    eprintln!("{} Failed to read license text: {}", "[ERROR]".red(), e);
+                    eprintln!("{} Failed to fetch license: {}", "[ERROR]".red(), e);
                     process::exit(1);
                 }
             };
@@ -224,9 +205,10 @@ impl LicenseManager {
             print!("{}", "End -----".bold())
         } else {
             println!(
-                "{} License '{}' not found in SPDX list. Please try again.",
+                "{} License '{}' not found in {}. Please try again.",
                 "[ERROR]".red(),
-                license.clone().red()
+                license.clone().red(),
+                Self::list_name(&license)
             );
             let similar_licenses = Tools::fuzzy_search(&license_list_lower, &license);
             if !similar_licenses.is_empty() {
@@ -239,6 +221,158 @@ impl LicenseManager {
         }
     }
 
+    /// Registers a custom license text from a file or a directory of files as `LicenseRef-<name>`
+    pub fn add_license(path: String, name: Option<String>, org: bool) {
+        let source = std::path::Path::new(&path);
+        let scope = if org {
+            StoreScope::Org
+        } else {
+            StoreScope::User
+        };
+        let files: Vec<std::path::PathBuf> = if source.is_dir() {
+            if name.is_some() {
+                eprintln!(
+                    "{} A name can only be given when adding a single file.",
+                    "[ERROR]".red()
+                );
+                process::exit(1);
+            }
+            match std::fs::read_dir(source) {
+                Ok(entries) => {
+                    let mut files: Vec<_> = entries
+                        .filter_map(|entry| entry.ok())
+                        .map(|entry| entry.path())
+                        .filter(|path| path.is_file())
+                        .collect();
+                    files.sort();
+                    files
+                }
+                Err(e) => {
+                    eprintln!("{} Failed to read directory: {}", "[ERROR]".red(), e);
+                    process::exit(1);
+                }
+            }
+        } else if source.is_file() {
+            vec![source.to_path_buf()]
+        } else {
+            eprintln!("{} '{}' does not exist.", "[ERROR]".red(), path);
+            process::exit(1);
+        };
+
+        for file in files {
+            let stem = file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let raw_name = name.clone().unwrap_or(stem);
+            let license_name = match Self::license_ref(&raw_name) {
+                Some(license_name) => license_name,
+                None => {
+                    eprintln!(
+                        "{} '{}' is not a valid license name. Use letters, digits, '.' and '-'.",
+                        "[ERROR]".red(),
+                        raw_name
+                    );
+                    process::exit(1);
+                }
+            };
+            let content = match std::fs::read_to_string(&file) {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!(
+                        "{} Failed to read {}: {}",
+                        "[ERROR]".red(),
+                        file.display(),
+                        e
+                    );
+                    process::exit(1);
+                }
+            };
+            match TemplateStore::add("licenses", &format!("{license_name}.txt"), &content, scope) {
+                Ok(stored) => println!(
+                    "{} Registered {} ({})",
+                    "[SUCCESS]".green(),
+                    license_name.clone().green(),
+                    stored.display()
+                ),
+                Err(e) => {
+                    eprintln!("{} Failed to store license: {}", "[ERROR]".red(), e);
+                    process::exit(1);
+                }
+            }
+        }
+    }
+
+    /// Returns the file names of all registered custom licenses
+    pub fn custom_licenses() -> Vec<String> {
+        Self::scoped_custom_licenses()
+            .into_iter()
+            .map(|(license, _)| license)
+            .collect()
+    }
+
+    /// Returns the registered custom licenses with the store they come from
+    ///
+    /// Only `LicenseRef-*.txt` files count, so other files in the store are ignored.
+    fn scoped_custom_licenses() -> Vec<(String, StoreScope)> {
+        TemplateStore::list("licenses")
+            .into_iter()
+            .filter(|(license, _)| {
+                license.starts_with(LICENSE_REF_PREFIX) && license.ends_with(".txt")
+            })
+            .collect()
+    }
+
+    /// Returns the licenses a name should be looked up in
+    fn lookup_list(license: &str) -> Vec<String> {
+        if Self::is_license_ref(license) {
+            return Self::custom_licenses();
+        }
+        match Self::get_licenses() {
+            Ok(files) => {
+                println!("{} Fetched Licenses", "[SUCCESS]".green());
+                files
+            }
+            Err(e) => {
+                eprintln!("{} Could not fetch licenses: {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Describes the list a name is looked up in
+    fn list_name(license: &str) -> &'static str {
+        if Self::is_license_ref(license) {
+            "the template store"
+        } else {
+            "SPDX list"
+        }
+    }
+
+    /// Checks if the license is a custom `LicenseRef-` license
+    pub fn is_license_ref(license: &str) -> bool {
+        license
+            .to_lowercase()
+            .starts_with(&LICENSE_REF_PREFIX.to_lowercase())
+    }
+
+    /// Turns a name into a `LicenseRef-<name>` identifier
+    fn license_ref(name: &str) -> Option<String> {
+        let name = if Self::is_license_ref(name) {
+            &name[LICENSE_REF_PREFIX.len()..]
+        } else {
+            name
+        };
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        {
+            return None;
+        }
+        Some(format!("{LICENSE_REF_PREFIX}{name}"))
+    }
+
     /// Returns a vector of license strings
     pub fn get_licenses() -> Result<Vec<String>, Box<dyn std::error::Error>> {
         {
@@ -267,7 +401,23 @@ impl LicenseManager {
     }
 
     /// Modifies the license either by asking the user or by refering to config
-    pub fn modify_license(mut license: String) -> String {
+    pub fn modify_license(license: String) -> String {
+        match Self::fill_placeholders(license, true) {
+            Ok(license) => license,
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Modifies the license using only the values stored in config, without prompting
+    pub fn modify_license_stored(license: String) -> Result<String, String> {
+        Self::fill_placeholders(license, false)
+    }
+
+    /// Replaces placeholders with config values, prompting for missing ones when `interactive`
+    fn fill_placeholders(mut license: String, interactive: bool) -> Result<String, String> {
         ConfigManager::load_config();
         let data_map = {
             let config_guard = CONFIGURATION.lock().unwrap();
@@ -276,6 +426,7 @@ impl LicenseManager {
 
         use std::collections::HashMap;
         let mut replacements = HashMap::new();
+        let mut problems: Vec<String> = Vec::new();
 
         let mut start = 0;
         while let Some(open_pos) = license[start..].find('<') {
@@ -284,28 +435,29 @@ impl LicenseManager {
                 let close_pos = open_pos + close_pos;
                 let placeholder = &license[open_pos + 1..close_pos];
 
-                if !placeholder.starts_with("http") && !replacements.contains_key(placeholder) {
-                    let value = if let Some(ref data) = data_map {
-                        if let Some(val) = data.get(placeholder) {
-                            val.clone()
-                        } else {
-                            let user_input: String = Input::new()
-                                .with_prompt(placeholder)
-                                .interact_text()
-                                .unwrap();
-
-                            replacements.insert(placeholder.to_string(), user_input.clone());
-                            user_input
+                let reported = problems
+                    .iter()
+                    .any(|p| p.starts_with(&format!("<{placeholder}>")));
+                if !placeholder.starts_with("http")
+                    && !replacements.contains_key(placeholder)
+                    && !reported
+                {
+                    let stored = data_map.as_ref().and_then(|data| data.get(placeholder));
+                    let value = match (stored, interactive) {
+                        (Some(val), true) => Ok(Placeholders::checked(placeholder, val)),
+                        (Some(val), false) => {
+                            Placeholders::validate(placeholder, val).map(|_| val.clone())
                         }
-                    } else {
-                        let user_input: String = Input::new()
-                            .with_prompt(placeholder)
-                            .interact_text()
-                            .unwrap();
-                        replacements.insert(placeholder.to_string(), user_input.clone());
-                        user_input
+                        (None, true) => Ok(Placeholders::prompt(placeholder)),
+                        (None, false) => Placeholders::default_value(placeholder)
+                            .ok_or_else(|| "no value stored in curator.json".to_string()),
                     };
-                    replacements.insert(placeholder.to_string(), value);
+                    match value {
+                        Ok(value) => {
+                            replacements.insert(placeholder.to_string(), value);
+                        }
+                        Err(e) => problems.push(format!("<{placeholder}>: {e}")),
+                    }
                 }
                 start = close_pos + 1;
             } else {
@@ -313,6 +465,13 @@ impl LicenseManager {
             }
         }
 
+        if !problems.is_empty() {
+            return Err(format!(
+                "Missing or invalid placeholder values:\n  {}",
+                problems.join("\n  ")
+            ));
+        }
+
         for (placeholder, value) in &replacements {
             let pattern = format!("<{placeholder}>");
             license = license.replace(&pattern, value);
@@ -351,11 +510,36 @@ impl LicenseManager {
         }
 
         ConfigManager::save_config();
-        license
+        Ok(license)
     }
 
     /// Downloads License file to `LICENSE`
-    fn download_license(license: String) -> Result<(), Box<dyn std::error::Error>> {
+    fn download_license(
+        license: String,
+        interactive: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut response_text = Self::license_text(&license)?;
+        response_text = if interactive {
+            Self::modify_license(response_text)
+        } else {
+            Self::modify_license_stored(response_text)?
+        };
+        let filename = "LICENSE".to_string();
+        std::fs::write(&filename, response_text)?;
+        println!(
+            "{}",
+            format!("Downloaded {license} license to {filename}").green()
+        );
+        Ok(())
+    }
+
+    /// Returns the raw text of a license from the template store or the SPDX list
+    pub fn license_text(license: &str) -> Result<String, Box<dyn std::error::Error>> {
+        if Self::is_license_ref(license) {
+            let (path, _) = TemplateStore::find("licenses", &format!("{license}.txt"))
+                .ok_or_else(|| format!("License '{license}' is not in the template store"))?;
+            return Ok(std::fs::read_to_string(path)?);
+        }
         let url = format!(
             "https://raw.githubusercontent.com/spdx/license-list-data/main/text/{license}.txt"
         );
@@ -374,14 +558,6 @@ impl LicenseManager {
             .into());
         }
 
-        let mut response_text = response.text()?;
-        response_text = Self::modify_license(response_text);
-        let filename = "LICENSE".to_string();
-        std::fs::write(&filename, response_text)?;
-        println!(
-            "{}",
-            format!("Downloaded {license} license to {filename}").green()
-        );
-        Ok(())
+        Ok(response.text()?)
     }
 }
@@ -1,5 +1,19 @@
 use clap::{Parser, Subcommand};
-use curator_cli::{conduct::ConductManager, *};
+use curator_cli::{
+    changelog::ChangelogManager,
+    citation::CitationManager,
+    codeowners::CodeownersManager,
+    conduct::ConductManager,
+    contributing::ContributingManager,
+    depsbot::DepsBotManager,
+    gitignore::GitignoreManager,
+    hygiene::{self, HygieneManager},
+    policy::PolicyManager,
+    readme::ReadmeManager,
+    security::SecurityManager,
+    templates::TemplatesManager,
+    *,
+};
 
 #[derive(Parser)]
 #[command(name = "cu")]
@@ -32,8 +46,78 @@ enum Commands {
         #[command(subcommand)]
         action: ProjectAction,
     },
+    /// Manage the contributing guide
+    Contributing {
+        #[command(subcommand)]
+        action: ContributingAction,
+    },
+    /// Manage the security policy
+    Security {
+        #[command(subcommand)]
+        action: SecurityAction,
+    },
+    /// Generate issue and pull request templates
+    Templates {
+        #[command(subcommand)]
+        action: TemplatesAction,
+    },
+    /// Generate and check CODEOWNERS
+    Codeowners {
+        #[command(subcommand)]
+        action: CodeownersAction,
+    },
+    /// Manage the README
+    Readme {
+        #[command(subcommand)]
+        action: ReadmeAction,
+    },
+    /// Manage CHANGELOG.md
+    Changelog {
+        #[command(subcommand)]
+        action: ChangelogAction,
+    },
+    /// Manage CITATION.cff
+    Citation {
+        #[command(subcommand)]
+        action: CitationAction,
+    },
+    /// Manage .gitignore templates
+    Gitignore {
+        #[command(subcommand)]
+        action: GitignoreAction,
+    },
+    /// Manage .editorconfig
+    Editorconfig {
+        #[command(subcommand)]
+        action: EditorconfigAction,
+    },
+    /// Manage .gitattributes
+    Gitattributes {
+        #[command(subcommand)]
+        action: GitattributesAction,
+    },
+    /// Configure a dependency update bot
+    DepsBot {
+        #[command(subcommand)]
+        action: DepsBotAction,
+    },
     /// Shows the Community Standards of the project
-    Standards,
+    Standards {
+        #[command(subcommand)]
+        action: Option<StandardsAction>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+        /// Policy file or URL to check against instead of the configured one
+        #[arg(long, global = true)]
+        policy: Option<String>,
+        /// Write the report as sarif or junit, to stdout unless --report-file is given
+        #[arg(long, conflicts_with = "json")]
+        report_format: Option<String>,
+        /// File to write the report to; the format follows its extension by default
+        #[arg(long)]
+        report_file: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Clone)]
@@ -54,16 +138,242 @@ enum LicenseAction {
         /// The name of the license to download and configure
         license_name: String,
     },
+    /// Register a custom license as `LicenseRef-<name>`
+    Add {
+        /// A license file, or a directory of license files
+        path: String,
+        /// The name to register a single file under
+        #[arg(long)]
+        name: Option<String>,
+        /// Store in the organization template directory (`CURATOR_ORG_DIR`)
+        #[arg(long)]
+        org: bool,
+    },
 }
 
 #[derive(Subcommand, Clone)]
 enum ConductAction {
     /// Set/download a Code of Conduct fot your project
-    Set,
+    Set {
+        /// The id of the code of conduct; prompts when omitted
+        conduct_id: Option<String>,
+        /// Install a custom code of conduct from a file path or URL
+        #[arg(long)]
+        from: Option<String>,
+        /// Store the custom code in the organization template directory (`CURATOR_ORG_DIR`)
+        #[arg(long, requires = "from")]
+        org: bool,
+        /// The version of the code, e.g. 2.1 (defaults to the newest)
+        #[arg(long)]
+        version: Option<String>,
+        /// The language of the code, e.g. en, de or ja (defaults to en)
+        #[arg(long)]
+        language: Option<String>,
+        /// Fetch the latest published template instead of the bundled one
+        #[arg(long)]
+        latest: bool,
+    },
     /// Remove Code of Conduct
     Remove,
+    /// Regenerate the Code of Conduct from the stored choices
+    Reload,
+    /// List all codes of conduct
+    List,
+    /// Show the code of conduct, version and language the project uses
+    Status,
+    /// Detect which code of conduct an existing file is based on
+    Detect {
+        /// The file to inspect (defaults to CODE_OF_CONDUCT.md)
+        path: Option<String>,
+    },
     /// Preview a code of conduct
-    Preview,
+    Preview {
+        /// The id of the code of conduct; prompts when omitted
+        conduct_id: Option<String>,
+        /// The version of the code, e.g. 2.1 (defaults to the newest)
+        #[arg(long)]
+        version: Option<String>,
+        /// The language of the code, e.g. en, de or ja (defaults to en)
+        #[arg(long)]
+        language: Option<String>,
+        /// Fetch the latest published template instead of the bundled one
+        #[arg(long)]
+        latest: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum ContributingAction {
+    /// Create CONTRIBUTING.md with build, test and lint commands for the project
+    Set {
+        /// Ecosystems to describe (rust, node, python, go); detected when omitted
+        #[arg(long = "ecosystem")]
+        ecosystems: Vec<String>,
+        /// Replace an existing contributing guide
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum SecurityAction {
+    /// Create SECURITY.md with a supported versions table from the git tags
+    Set,
+    /// Refresh the supported versions table after new releases
+    Reload,
+    /// Create .well-known/security.txt (RFC 9116) from curator.json
+    Txt,
+}
+
+#[derive(Subcommand, Clone)]
+enum TemplatesAction {
+    /// Write .github/ISSUE_TEMPLATE and pull_request_template.md
+    Github {
+        /// Write issue forms (YAML) instead of Markdown templates
+        #[arg(long)]
+        forms: bool,
+        /// Overwrite existing templates
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum CodeownersAction {
+    /// Propose CODEOWNERS from who committed to each directory
+    Generate {
+        /// How far back to look, in any format git understands
+        #[arg(long, default_value = "1 year ago")]
+        since: String,
+        /// Directory depth to assign owners at
+        #[arg(long, default_value_t = 1)]
+        depth: usize,
+        /// Most owners listed per directory
+        #[arg(long, default_value_t = 2)]
+        max_owners: usize,
+        /// Write the file without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Map a commit email to a forge username
+    Map { email: String, username: String },
+    /// List tracked files without an owner
+    Check,
+}
+
+#[derive(Subcommand, Clone)]
+enum ReadmeAction {
+    /// Create README.md with sections and badges for the project
+    Init {
+        /// Replace an existing README.md
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check README sections, license, relative links and badges
+    Lint,
+}
+
+#[derive(Subcommand, Clone)]
+enum ChangelogAction {
+    /// Write or update CHANGELOG.md from conventional commits between tags
+    Generate {
+        /// Regenerate released versions instead of keeping them as written
+        #[arg(long)]
+        rewrite: bool,
+    },
+    /// Add an entry under Unreleased
+    Add {
+        /// Section such as added, changed or fixed, or a commit type such as feat
+        #[arg(value_name = "TYPE")]
+        kind: String,
+        message: String,
+    },
+    /// Move the Unreleased entries under a dated version heading
+    Release { version: String },
+}
+
+#[derive(Subcommand, Clone)]
+enum CitationAction {
+    /// Create CITATION.cff from the project metadata
+    Set,
+    /// Validate CITATION.cff against the CFF 1.2.0 schema
+    Check,
+}
+
+#[derive(Subcommand, Clone)]
+enum GitignoreAction {
+    /// Add or refresh template blocks, detecting the ecosystem when none are given
+    Add { templates: Vec<String> },
+    /// Refresh all blocks managed by curator
+    Update,
+    /// Remove template blocks, keeping your own lines
+    Remove {
+        #[arg(required = true)]
+        templates: Vec<String>,
+    },
+    /// List the bundled templates
+    List,
+}
+
+#[derive(Subcommand, Clone)]
+enum EditorconfigAction {
+    /// Create .editorconfig for the languages in the project
+    Set {
+        /// Replace an existing .editorconfig
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum GitattributesAction {
+    /// Create .gitattributes with normalization, linguist markers and LFS patterns
+    Set {
+        /// Replace an existing .gitattributes
+        #[arg(long)]
+        force: bool,
+        /// Size in MiB from which binary files are tracked with Git LFS
+        #[arg(long, default_value_t = hygiene::DEFAULT_LFS_THRESHOLD)]
+        lfs_threshold: u64,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum DepsBotAction {
+    /// Write the config of dependabot or renovate for the detected ecosystems
+    Set {
+        /// dependabot or renovate
+        bot: String,
+        /// Replace an existing config
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum StandardsAction {
+    /// Generate the missing community files
+    Fix {
+        /// Generate everything from curator.json without asking
+        #[arg(long)]
+        auto: bool,
+    },
+    /// Choose the organization policy the project is checked against
+    Policy {
+        #[command(subcommand)]
+        action: PolicyAction,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum PolicyAction {
+    /// Check the project against a policy file or URL
+    Set {
+        /// Path or URL of a `.toml` or `.json` policy
+        source: String,
+    },
+    /// Stop using the configured policy
+    Remove,
 }
 
 #[derive(Subcommand, Clone)]
@@ -99,16 +409,43 @@ fn main() {
             LicenseAction::Preview { license_name } => {
                 LicenseManager::preview_license(license_name);
             }
+            LicenseAction::Add { path, name, org } => {
+                LicenseManager::add_license(path, name, org);
+            }
         },
         Commands::Conduct { action } => match action {
-            ConductAction::Set => {
-                ConductManager::set_conduct();
+            ConductAction::Set {
+                conduct_id,
+                from,
+                org,
+                version,
+                language,
+                latest,
+            } => {
+                ConductManager::set_conduct(conduct_id, version, language, latest, from, org);
             }
             ConductAction::Remove => {
                 ConductManager::remove_conduct();
             }
-            ConductAction::Preview => {
-                ConductManager::preview_conduct();
+            ConductAction::Reload => {
+                ConductManager::reload_conduct();
+            }
+            ConductAction::List => {
+                ConductManager::list_conducts();
+            }
+            ConductAction::Status => {
+                ConductManager::conduct_status();
+            }
+            ConductAction::Detect { path } => {
+                ConductManager::detect_conduct(path);
+            }
+            ConductAction::Preview {
+                conduct_id,
+                version,
+                language,
+                latest,
+            } => {
+                ConductManager::preview_conduct(conduct_id, version, language, latest);
             }
         },
         Commands::Config { action } => match action {
@@ -124,6 +461,115 @@ fn main() {
                 ProjectManager::init(project_type);
             }
         },
-        Commands::Standards => Miscellaneous::standards(),
+        Commands::Contributing { action } => match action {
+            ContributingAction::Set { ecosystems, force } => {
+                ContributingManager::set_contributing(ecosystems, force);
+            }
+        },
+        Commands::Security { action } => match action {
+            SecurityAction::Set => {
+                SecurityManager::set_security();
+            }
+            SecurityAction::Reload => {
+                SecurityManager::reload_security();
+            }
+            SecurityAction::Txt => {
+                SecurityManager::generate_txt(true);
+            }
+        },
+        Commands::Templates { action } => match action {
+            TemplatesAction::Github { forms, force } => {
+                TemplatesManager::github(forms, force);
+            }
+        },
+        Commands::Codeowners { action } => match action {
+            CodeownersAction::Generate {
+                since,
+                depth,
+                max_owners,
+                yes,
+            } => {
+                CodeownersManager::generate_codeowners(&since, depth, max_owners, yes);
+            }
+            CodeownersAction::Map { email, username } => {
+                CodeownersManager::map_owner(email, username);
+            }
+            CodeownersAction::Check => {
+                CodeownersManager::check_codeowners();
+            }
+        },
+        Commands::Readme { action } => match action {
+            ReadmeAction::Init { force } => {
+                ReadmeManager::init_readme(force);
+            }
+            ReadmeAction::Lint => {
+                ReadmeManager::lint_readme();
+            }
+        },
+        Commands::Changelog { action } => match action {
+            ChangelogAction::Generate { rewrite } => {
+                ChangelogManager::generate_changelog(rewrite);
+            }
+            ChangelogAction::Add { kind, message } => {
+                ChangelogManager::add_entry(kind, message);
+            }
+            ChangelogAction::Release { version } => {
+                ChangelogManager::release(version);
+            }
+        },
+        Commands::Citation { action } => match action {
+            CitationAction::Set => {
+                CitationManager::set_citation();
+            }
+            CitationAction::Check => {
+                CitationManager::check_citation();
+            }
+        },
+        Commands::Gitignore { action } => match action {
+            GitignoreAction::Add { templates } => {
+                GitignoreManager::add_templates(templates);
+            }
+            GitignoreAction::Update => {
+                GitignoreManager::update_templates();
+            }
+            GitignoreAction::Remove { templates } => {
+                GitignoreManager::remove_templates(templates);
+            }
+            GitignoreAction::List => {
+                GitignoreManager::list_templates();
+            }
+        },
+        Commands::Editorconfig { action } => match action {
+            EditorconfigAction::Set { force } => {
+                HygieneManager::editorconfig(force);
+            }
+        },
+        Commands::Gitattributes { action } => match action {
+            GitattributesAction::Set {
+                force,
+                lfs_threshold,
+            } => {
+                HygieneManager::gitattributes(force, lfs_threshold);
+            }
+        },
+        Commands::DepsBot { action } => match action {
+            DepsBotAction::Set { bot, force } => {
+                DepsBotManager::set_bot(bot, force);
+            }
+        },
+        Commands::Standards {
+            action,
+            json,
+            policy,
+            report_format,
+            report_file,
+        } => match action {
+            Some(StandardsAction::Fix { auto }) => Miscellaneous::standards_fix(auto, policy),
+            Some(StandardsAction::Policy { action }) => match action {
+                PolicyAction::Set { source } => PolicyManager::set_policy(source),
+                PolicyAction::Remove => PolicyManager::remove_policy(),
+            },
+            None => Miscellaneous::standards(json, policy, report_format, report_file),
+        },
     }
 }
@@ -1,49 +1,250 @@
 use crossterm::style::Stylize;
+use dialoguer::Input;
+use std::process;
+
+use crate::codeowners::CodeownersManager;
+use crate::conduct::{ConductManager, ConductRegistry};
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::contributing::ContributingManager;
+use crate::license::LicenseManager;
+use crate::policy::Policy;
+use crate::readme::ReadmeManager;
+use crate::report::ReportFormat;
+use crate::security::SecurityManager;
+use crate::standards::StandardsReport;
+use crate::support::SupportManager;
+use crate::templates::TemplatesManager;
+use crate::tools::Tools;
+
+/// How many failing files a check lists before summarizing the rest
+const MAX_LISTED_FILES: usize = 10;
 
 pub struct Miscellaneous;
 
 impl Miscellaneous {
-    pub fn standards() {
-        println!("{}", "Community Standards".bold());
-        println!(
-            "{}",
-            if std::path::Path::new("README.md").exists() {
-                "README.md".green()
-            } else {
-                "README.md".red()
+    /// Shows the community standards of the project
+    pub fn standards(
+        json: bool,
+        policy: Option<String>,
+        report_format: Option<String>,
+        report_file: Option<String>,
+    ) {
+        let report = Self::standards_report(policy);
+        if report_format.is_some() || report_file.is_some() {
+            let format = match (&report_format, &report_file) {
+                (Some(id), _) => ReportFormat::from_id(id),
+                (None, Some(file)) => ReportFormat::from_path(file),
+                (None, None) => None,
+            };
+            let Some(format) = format else {
+                let known: Vec<&str> = ReportFormat::ALL.iter().map(|f| f.id()).collect();
+                eprintln!(
+                    "{} Unknown report format. Use --report-format with one of: {}",
+                    "[ERROR]".red(),
+                    known.join(", ")
+                );
+                process::exit(1);
+            };
+            let rendered = format.render(&report);
+            let Some(file) = report_file else {
+                print!("{rendered}");
+                return;
+            };
+            if let Err(e) = Tools::write_file(&file, &rendered) {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
             }
-        );
-        println!(
-            "{}",
-            if std::path::Path::new("LICENSE").exists() {
-                "README.md".green()
-            } else {
-                "README.md".red()
+            println!(
+                "{} Wrote {} report to {}",
+                "[SUCCESS]".green(),
+                format.id().to_uppercase(),
+                file
+            );
+            return;
+        }
+        if json {
+            match serde_json::to_string_pretty(&report) {
+                Ok(json) => println!("{json}"),
+                Err(e) => {
+                    eprintln!("{} Failed to serialize report: {}", "[ERROR]".red(), e);
+                    process::exit(1);
+                }
             }
-        );
-        println!(
-            "{}",
-            if std::path::Path::new("CODE_OF_CONDUCT.md").exists() {
-                "CODE_OF_CONDUCT.md".green()
+            return;
+        }
+
+        match &report.policy {
+            Some(policy) => println!(
+                "{} {}",
+                "Community Standards".bold(),
+                format!("({policy})").grey()
+            ),
+            None => println!("{}", "Community Standards".bold()),
+        }
+        for check in &report.checks {
+            if check.passed {
+                match &check.path {
+                    Some(path) => println!(
+                        "{} {} {}",
+                        "✓".green(),
+                        check.title.clone().green(),
+                        format!("({path})").grey()
+                    ),
+                    None => println!("{} {}", "✓".green(), check.title.clone().green()),
+                }
+                continue;
+            }
+            if check.required {
+                println!("{} {}", "✗".red(), check.title.clone().red());
             } else {
-                "CODE_OF_CONDUCT.md".red()
+                println!(
+                    "{} {} {}",
+                    "○".yellow(),
+                    check.title.clone().yellow(),
+                    "(optional)".grey()
+                );
+            }
+            println!("    {}", check.explanation);
+            if check.files.is_empty() {
+                println!("    Looked for: {}", check.locations.join(", ").grey());
+            }
+            for file in check.files.iter().take(MAX_LISTED_FILES) {
+                println!("    {}", file.clone().grey());
+            }
+            if check.files.len() > MAX_LISTED_FILES {
+                println!(
+                    "    {}",
+                    format!("...and {} more", check.files.len() - MAX_LISTED_FILES).grey()
+                );
             }
+        }
+        for warning in &report.warnings {
+            println!("{} {}", "[WARNING]".yellow(), warning);
+        }
+        let score = format!(
+            "Score: {}% ({}/{})",
+            report.percent, report.score, report.max_score
         );
         println!(
             "{}",
-            if std::path::Path::new("CONTRIBUTING.md").exists() {
-                "CONTRIBUTING.md".green()
-            } else {
-                "CONTRIBUTING.md".red()
+            match report.percent {
+                80.. => score.green(),
+                50..=79 => score.yellow(),
+                _ => score.red(),
             }
         );
-        println!(
-            "{}",
-            if std::path::Path::new("SECURITY.md").exists() {
-                "SECURITY.md".green()
-            } else {
-                "SECURITY.md".red()
+    }
+
+    /// Generates the community files the standards report lists as missing
+    ///
+    /// Asks before each file unless `auto`, in which case only `curator.json` data is used
+    /// and files the policy marks optional are skipped.
+    pub fn standards_fix(auto: bool, policy: Option<String>) {
+        ConfigManager::load_config();
+        let report = Self::standards_report(policy.clone());
+        if report.missing().next().is_none() {
+            println!("{} All community standards are met.", "[SUCCESS]".green());
+            return;
+        }
+
+        let mut failed = Vec::new();
+        for check in report.missing() {
+            if auto && !check.required {
+                continue;
+            }
+            let generator: Option<fn(bool) -> Result<(), String>> = match check.id.as_str() {
+                "readme" => Some(ReadmeManager::generate),
+                "license" => Some(LicenseManager::regenerate_license),
+                "code-of-conduct" => Some(Self::fix_conduct),
+                "contributing" => Some(ContributingManager::generate),
+                "security" => Some(SecurityManager::generate),
+                "support" => Some(SupportManager::generate),
+                "issue-templates" => Some(TemplatesManager::generate_issue_templates),
+                "pull-request-template" => Some(TemplatesManager::generate_pull_request_template),
+                "codeowners" => Some(CodeownersManager::generate),
+                _ => None,
+            };
+            let Some(generator) = generator else {
+                println!(
+                    "{} No generator for {} yet, skipping.",
+                    "[INFO]".yellow(),
+                    check.title
+                );
+                continue;
+            };
+            if !auto {
+                println!("{} {}", check.title.clone().red(), check.explanation);
+                let generate = Input::<String>::new()
+                    .with_prompt(format!("Generate {}? (y/n)", check.title))
+                    .default("y".into())
+                    .interact_text()
+                    .unwrap()
+                    .to_lowercase()
+                    .starts_with('y');
+                if !generate {
+                    continue;
+                }
             }
+            if let Err(e) = generator(!auto) {
+                eprintln!(
+                    "{} Failed to generate {}: {}",
+                    "[ERROR]".red(),
+                    check.title,
+                    e
+                );
+                failed.push(check.title.clone());
+            }
+        }
+
+        let report = Self::standards_report(policy);
+        println!(
+            "Score: {}% ({}/{})",
+            report.percent, report.score, report.max_score
         );
+        if !failed.is_empty() {
+            eprintln!(
+                "{} Could not generate: {}",
+                "[ERROR]".red(),
+                failed.join(", ")
+            );
+            process::exit(1);
+        }
+    }
+
+    /// Evaluates the project against its policy, or the built-in standards when it has none
+    fn standards_report(policy: Option<String>) -> StandardsReport {
+        let root = std::path::Path::new(".");
+        if ConfigManager::check_config() {
+            ConfigManager::load_config();
+        }
+        let Some(source) = Policy::locate(root, policy) else {
+            return StandardsReport::evaluate(root);
+        };
+        match Policy::load(&source) {
+            Ok(policy) => policy.evaluate(root),
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Creates the code of conduct, falling back to the Contributor Covenant in automatic mode
+    ///
+    /// A new choice is only saved to `curator.json` once the file has been written.
+    fn fix_conduct(interactive: bool) -> Result<(), String> {
+        let configured = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .get("data")
+                .and_then(|data| data.get("conduct"))
+                .cloned()
+        };
+        let id = match configured {
+            Some(id) => id,
+            None if interactive => ConductRegistry::load().prompt().id.clone(),
+            None => "contributor-covenant".to_string(),
+        };
+        ConductManager::write_conduct(&id, interactive)
     }
 }
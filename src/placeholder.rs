@@ -0,0 +1,248 @@
+use chrono::Datelike;
+use chrono::Local;
+use crossterm::style::Stylize;
+use dialoguer::Input;
+
+use crate::config::CONFIGURATION;
+
+/// The kind of value a template placeholder expects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceholderKind {
+    Email,
+    Url,
+    Year,
+    Text,
+}
+
+impl PlaceholderKind {
+    /// Checks that a value is acceptable for this kind of placeholder
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("A value is required".to_string());
+        }
+        match self {
+            PlaceholderKind::Email => {
+                let valid = match value.split_once('@') {
+                    Some((local, domain)) => {
+                        !local.is_empty()
+                            && !domain.contains('@')
+                            && domain.contains('.')
+                            && !domain.starts_with('.')
+                            && !domain.ends_with('.')
+                            && !value.chars().any(char::is_whitespace)
+                    }
+                    None => false,
+                };
+                if valid {
+                    Ok(())
+                } else {
+                    Err(format!("'{value}' is not a valid email address"))
+                }
+            }
+            PlaceholderKind::Url => {
+                let rest = value
+                    .strip_prefix("https://")
+                    .or_else(|| value.strip_prefix("http://"));
+                match rest {
+                    Some(rest)
+                        if !rest.is_empty()
+                            && !rest.starts_with('/')
+                            && !value.chars().any(char::is_whitespace) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(format!(
+                        "'{value}' is not a valid URL (it must start with http:// or https://)"
+                    )),
+                }
+            }
+            PlaceholderKind::Year => {
+                let max = Local::now().year() + 1;
+                let valid_year = |year: &str| {
+                    year.len() == 4
+                        && year
+                            .parse::<i32>()
+                            .is_ok_and(|year| (1970..=max).contains(&year))
+                };
+                let valid = match value.split_once('-') {
+                    Some((from, to)) => {
+                        valid_year(from.trim()) && valid_year(to.trim()) && from.trim() <= to.trim()
+                    }
+                    None => valid_year(value),
+                };
+                if valid {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "'{value}' is not a valid year (use YYYY or YYYY-YYYY, up to {max})"
+                    ))
+                }
+            }
+            PlaceholderKind::Text => Ok(()),
+        }
+    }
+}
+
+/// Describes a placeholder found in license and conduct templates
+#[derive(Clone, Debug)]
+pub struct PlaceholderSpec {
+    /// The placeholder text between `<` and `>`
+    pub key: String,
+    pub kind: PlaceholderKind,
+    pub description: String,
+    /// Default value; `{year}`, `{project}`, `{author}` and `{<data key>}` are filled from the configuration
+    pub default: Option<String>,
+}
+
+/// Known placeholders as `(key, kind, description, default)`
+const SCHEMA: &[(&str, PlaceholderKind, &str, Option<&str>)] = &[
+    (
+        "year",
+        PlaceholderKind::Year,
+        "Copyright year",
+        Some("{year}"),
+    ),
+    (
+        "copyright holders",
+        PlaceholderKind::Text,
+        "Name of the copyright holders",
+        Some("{author}"),
+    ),
+    (
+        "email",
+        PlaceholderKind::Email,
+        "Email address where conduct reports are sent",
+        None,
+    ),
+    (
+        "security-email",
+        PlaceholderKind::Email,
+        "Email address where vulnerability reports are sent",
+        Some("{email}"),
+    ),
+    (
+        "license",
+        PlaceholderKind::Text,
+        "SPDX identifier of the project license",
+        None,
+    ),
+    (
+        "community-name",
+        PlaceholderKind::Text,
+        "Name of the project or community",
+        Some("{project}"),
+    ),
+    (
+        "governing-body",
+        PlaceholderKind::Text,
+        "Organization or team that governs the community",
+        None,
+    ),
+    (
+        "link-to-reporting-guidelines",
+        PlaceholderKind::Url,
+        "URL of the guidelines for reporting violations",
+        None,
+    ),
+    (
+        "link-to-grievance-policy",
+        PlaceholderKind::Url,
+        "URL of the policy for addressing grievances",
+        None,
+    ),
+    (
+        "faq",
+        PlaceholderKind::Url,
+        "URL of the frequently asked questions about the code of conduct",
+        None,
+    ),
+    (
+        "contact-info(You-can-contact-us-at...)",
+        PlaceholderKind::Text,
+        "How people can contact the community organizers (e.g. an email or phone number)",
+        None,
+    ),
+    (
+        "type-of-citizenship(source/culture/tech)",
+        PlaceholderKind::Text,
+        "Kind of open citizenship the community promotes (source, culture or tech)",
+        Some("source"),
+    ),
+];
+
+pub struct Placeholders;
+
+impl Placeholders {
+    /// Returns the schema entry for a placeholder, treating unknown ones as free text
+    pub fn spec(placeholder: &str) -> PlaceholderSpec {
+        match SCHEMA
+            .iter()
+            .find(|(key, ..)| key.eq_ignore_ascii_case(placeholder))
+        {
+            Some((key, kind, description, default)) => PlaceholderSpec {
+                key: key.to_string(),
+                kind: *kind,
+                description: description.to_string(),
+                default: default.map(str::to_string),
+            },
+            None => PlaceholderSpec {
+                key: placeholder.to_string(),
+                kind: PlaceholderKind::Text,
+                description: placeholder.to_string(),
+                default: None,
+            },
+        }
+    }
+
+    /// Checks a value against the schema entry for a placeholder
+    pub fn validate(placeholder: &str, value: &str) -> Result<(), String> {
+        Self::spec(placeholder).kind.validate(value)
+    }
+
+    /// Returns the default value for a placeholder with configuration variables filled in
+    pub fn default_value(placeholder: &str) -> Option<String> {
+        let default = Self::spec(placeholder).default?;
+        let (settings, data) = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            (
+                config_guard.get("settings").cloned().unwrap_or_default(),
+                config_guard.get("data").cloned().unwrap_or_default(),
+            )
+        };
+        let project = settings.get("project").cloned().unwrap_or_default();
+        let author = settings.get("author").cloned().unwrap_or_default();
+        let mut value = default
+            .replace("{year}", &Local::now().year().to_string())
+            .replace("{project}", &project)
+            .replace("{author}", &author);
+        for (key, data_value) in &data {
+            value = value.replace(&format!("{{{key}}}"), data_value);
+        }
+        (!value.trim().is_empty() && !value.contains('{')).then_some(value)
+    }
+
+    /// Asks the user for a placeholder value until it passes validation
+    pub fn prompt(placeholder: &str) -> String {
+        let spec = Self::spec(placeholder);
+        let kind = spec.kind;
+        let mut input = Input::<String>::new()
+            .with_prompt(spec.description)
+            .validate_with(move |value: &String| kind.validate(value));
+        if let Some(default) = Self::default_value(placeholder) {
+            input = input.default(default);
+        }
+        input.interact_text().unwrap().trim().to_string()
+    }
+
+    /// Returns a stored value, asking again when it does not pass validation
+    pub fn checked(placeholder: &str, value: &str) -> String {
+        match Self::validate(placeholder, value) {
+            Ok(()) => value.to_string(),
+            Err(e) => {
+                println!("{} {}", "[WARNING]".yellow(), e);
+                Self::prompt(placeholder)
+            }
+        }
+    }
+}
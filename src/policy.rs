@@ -0,0 +1,658 @@
+use crossterm::style::Stylize;
+use once_cell::sync::Lazy;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use crate::conduct::{ConductManager, ConductRegistry};
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::security::SecurityManager;
+use crate::standards::{StandardCheck, StandardsReport};
+use crate::tools::Tools;
+
+static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .user_agent("curator-app")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+/// Policy files looked for in the project root when none is configured
+const DEFAULT_FILES: &[&str] = &["curator-policy.toml", "curator-policy.json"];
+
+/// Organization rules a project is checked against instead of the built-in standards
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Policy {
+    /// Shown in the report, defaults to where the policy was loaded from
+    pub name: Option<String>,
+    pub files: FileRules,
+    pub licenses: LicenseRules,
+    pub conduct: ConductRules,
+    pub headers: Vec<HeaderRule>,
+}
+
+/// Community files and paths, by standard id (such as `security`) or glob
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FileRules {
+    pub required: Vec<String>,
+    pub optional: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LicenseRules {
+    /// SPDX ids the project license must be one of
+    pub allowed: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ConductRules {
+    /// Code of conduct id, as listed by `cu conduct list`
+    pub required: Option<String>,
+}
+
+/// Text that must appear near the top of matching files
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct HeaderRule {
+    /// Stable id for reports, such as `spdx` for `header-spdx`; defaults to the name in kebab-case
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// May use `{license}`, `{year}`, `{author}` and `{project}`
+    pub text: String,
+    /// How many lines from the top of the file are searched
+    #[serde(default = "HeaderRule::default_lines")]
+    pub lines: usize,
+}
+
+impl HeaderRule {
+    fn default_lines() -> usize {
+        10
+    }
+
+    /// The report id, which stays the same when rules are added or reordered
+    pub fn check_id(&self) -> Option<String> {
+        let slug = |text: &str| {
+            text.to_lowercase()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join("-")
+        };
+        self.id
+            .as_deref()
+            .or(self.name.as_deref())
+            .map(slug)
+            .filter(|id| !id.is_empty())
+            .map(|id| format!("header-{id}"))
+    }
+}
+
+impl Policy {
+    /// Reads a policy from a `.toml` or `.json` file or URL
+    pub fn load(source: &str) -> Result<Self, String> {
+        let text = if source.starts_with("http://") || source.starts_with("https://") {
+            HTTP_CLIENT
+                .get(source)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.text())
+                .map_err(|e| format!("Failed to download policy {source}: {e}"))?
+        } else {
+            fs::read_to_string(source)
+                .map_err(|e| format!("Failed to read policy {source}: {e}"))?
+        };
+        let mut policy: Self = if source.to_lowercase().ends_with(".json") {
+            serde_json::from_str(&text).map_err(|e| format!("Invalid policy {source}: {e}"))?
+        } else {
+            toml::from_str(&text).map_err(|e| format!("Invalid policy {source}: {e}"))?
+        };
+        policy.validate()?;
+        policy.name.get_or_insert_with(|| source.to_string());
+        Ok(policy)
+    }
+
+    /// Finds the policy to use: the given one, `settings.policy` in `curator.json`,
+    /// or a `curator-policy.toml`/`.json` in the project root
+    pub fn locate(root: &Path, source: Option<String>) -> Option<String> {
+        source
+            .or_else(|| {
+                let config_guard = CONFIGURATION.lock().unwrap();
+                config_guard
+                    .get("settings")
+                    .and_then(|settings| settings.get("policy"))
+                    .cloned()
+            })
+            .or_else(|| {
+                DEFAULT_FILES
+                    .iter()
+                    .map(|file| root.join(file))
+                    .find(|path| path.is_file())
+                    .map(|path| path.to_string_lossy().to_string())
+            })
+    }
+
+    /// Checks a project directory against the policy
+    pub fn evaluate(&self, root: &Path) -> StandardsReport {
+        let mut checks = Vec::new();
+        if self.files.required.is_empty() && self.files.optional.is_empty() {
+            checks.extend(
+                StandardsReport::standard_ids()
+                    .into_iter()
+                    .filter_map(|id| StandardsReport::check_standard(root, id)),
+            );
+        } else {
+            let files = Tools::project_files(root);
+            let entries = self
+                .files
+                .required
+                .iter()
+                .map(|entry| (entry, true))
+                .chain(self.files.optional.iter().map(|entry| (entry, false)));
+            for (entry, required) in entries {
+                let mut check = StandardsReport::check_standard(root, entry)
+                    .unwrap_or_else(|| Self::check_path(root, &files, entry));
+                check.required = required;
+                checks.push(check);
+            }
+        }
+
+        let data = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard.get("data").cloned().unwrap_or_default()
+        };
+        if !self.licenses.allowed.is_empty() {
+            checks.push(self.check_license(root, data.get("license")));
+        }
+        if let Some(required) = &self.conduct.required {
+            checks.push(Self::check_conduct(root, required, data.get("conduct")));
+        }
+        if !self.headers.is_empty() {
+            let files = Tools::project_files(root);
+            for rule in &self.headers {
+                checks.push(Self::check_header(root, &files, rule));
+            }
+        }
+
+        let mut report = StandardsReport::from_checks(checks);
+        report.policy = Some(self.name.clone().unwrap_or_else(|| "policy".to_string()));
+        report
+            .warnings
+            .extend(SecurityManager::txt_expiry_warning(root));
+        report
+    }
+
+    /// Rejects policies that cannot be evaluated, such as header rules without patterns
+    fn validate(&self) -> Result<(), String> {
+        let mut ids = Vec::new();
+        for (index, rule) in self.headers.iter().enumerate() {
+            if rule.include.is_empty() {
+                return Err(format!("Header rule {} has no include patterns", index + 1));
+            }
+            if rule.text.trim().is_empty() {
+                return Err(format!("Header rule {} has no text", index + 1));
+            }
+            let Some(id) = rule.check_id() else {
+                return Err(format!(
+                    "Header rule {} needs an id or a name to report it under",
+                    index + 1
+                ));
+            };
+            if ids.contains(&id) {
+                return Err(format!(
+                    "Header rule {} has the same id as an earlier rule: {id}",
+                    index + 1
+                ));
+            }
+            ids.push(id);
+        }
+        Ok(())
+    }
+
+    /// Checks for a path or glob that is not one of the known community files
+    fn check_path(root: &Path, files: &[std::path::PathBuf], entry: &str) -> StandardCheck {
+        let path = if entry.contains(['*', '?']) {
+            files
+                .iter()
+                .map(|file| file.to_string_lossy().replace('\\', "/"))
+                .find(|file| Tools::glob_match(entry, file))
+        } else {
+            root.join(entry).exists().then(|| entry.to_string())
+        };
+        StandardCheck {
+            id: entry.to_string(),
+            title: entry.to_string(),
+            passed: path.is_some(),
+            path,
+            weight: 1,
+            explanation: "Listed in the organization policy.".to_string(),
+            locations: vec![entry.to_string()],
+            required: true,
+            files: Vec::new(),
+        }
+    }
+
+    /// Checks the license in `curator.json` against the allowed ones
+    fn check_license(&self, root: &Path, license: Option<&String>) -> StandardCheck {
+        let passed = license.is_some_and(|license| {
+            self.licenses
+                .allowed
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(license))
+        });
+        let found = StandardsReport::check_standard(root, "license").and_then(|check| check.path);
+        let explanation = match license {
+            Some(license) => format!(
+                "The project uses {license}; the policy allows {}.",
+                self.licenses.allowed.join(", ")
+            ),
+            None => format!(
+                "No license is set in curator.json; the policy allows {}.",
+                self.licenses.allowed.join(", ")
+            ),
+        };
+        StandardCheck {
+            id: "allowed-license".to_string(),
+            title: "Allowed license".to_string(),
+            passed,
+            path: if passed { found.clone() } else { None },
+            weight: 3,
+            explanation,
+            locations: vec!["curator.json".to_string()],
+            required: true,
+            files: if passed {
+                Vec::new()
+            } else {
+                found.into_iter().collect()
+            },
+        }
+    }
+
+    /// Checks that the code of conduct is the required one, by its text or else by id
+    ///
+    /// The configured id only counts when the file does not match any known code.
+    fn check_conduct(root: &Path, required: &str, configured: Option<&String>) -> StandardCheck {
+        let found =
+            StandardsReport::check_standard(root, "code-of-conduct").and_then(|check| check.path);
+        let identified = found.as_ref().and_then(|path| {
+            let text = fs::read_to_string(root.join(path)).ok()?;
+            let registry = ConductRegistry::load();
+            ConductManager::identify(&registry, &text).map(|(conduct, _, _)| conduct.id.clone())
+        });
+        let detected = identified.or_else(|| configured.cloned());
+        let passed = found.is_some() && detected.as_deref() == Some(required);
+        let explanation = match &detected {
+            Some(id) if found.is_some() => {
+                format!("The policy requires the {required} code of conduct, found {id}.")
+            }
+            _ => format!("The policy requires the {required} code of conduct."),
+        };
+        StandardCheck {
+            id: "required-conduct".to_string(),
+            title: "Required code of conduct".to_string(),
+            passed,
+            path: if passed { found.clone() } else { None },
+            weight: 2,
+            explanation,
+            locations: vec!["CODE_OF_CONDUCT".to_string()],
+            required: true,
+            files: if passed {
+                Vec::new()
+            } else {
+                found.into_iter().collect()
+            },
+        }
+    }
+
+    /// Lists the files matched by a header rule that lack its text
+    fn check_header(root: &Path, files: &[std::path::PathBuf], rule: &HeaderRule) -> StandardCheck {
+        let text = Self::header_text(&rule.text);
+        let mut matched = 0;
+        let mut failing = Vec::new();
+        for file in files {
+            let name = file.to_string_lossy().replace('\\', "/");
+            if !rule
+                .include
+                .iter()
+                .any(|glob| Tools::glob_match(glob, &name))
+                || rule
+                    .exclude
+                    .iter()
+                    .any(|glob| Tools::glob_match(glob, &name))
+            {
+                continue;
+            }
+            matched += 1;
+            let Ok(content) = fs::read_to_string(root.join(file)) else {
+                continue;
+            };
+            let head: Vec<&str> = content.lines().take(rule.lines).collect();
+            if !head.join("\n").contains(&text) {
+                failing.push(name);
+            }
+        }
+        let title = rule
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Header in {}", rule.include.join(", ")));
+        StandardCheck {
+            id: rule.check_id().unwrap_or_else(|| "header".to_string()),
+            title,
+            passed: failing.is_empty(),
+            path: None,
+            weight: 2,
+            explanation: format!(
+                "{matched} matching files; the first {} lines must contain: {}",
+                rule.lines,
+                text.lines().next().unwrap_or_default()
+            ),
+            locations: rule.include.clone(),
+            required: true,
+            files: failing,
+        }
+    }
+
+    /// Fills the placeholders of a header rule from `curator.json`
+    fn header_text(text: &str) -> String {
+        let config_guard = CONFIGURATION.lock().unwrap();
+        let get = |section: &str, key: &str| {
+            config_guard
+                .get(section)
+                .and_then(|values| values.get(key))
+                .cloned()
+                .unwrap_or_default()
+        };
+        text.trim()
+            .replace("{license}", &get("data", "license"))
+            .replace("{year}", &get("data", "year"))
+            .replace("{author}", &get("settings", "author"))
+            .replace("{project}", &get("settings", "project"))
+    }
+}
+
+pub struct PolicyManager;
+
+impl PolicyManager {
+    /// Stores the policy in `settings.policy` after checking that it can be loaded
+    pub fn set_policy(source: String) {
+        ConfigManager::load_config();
+        let policy = match Policy::load(&source) {
+            Ok(policy) => policy,
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        };
+        {
+            let mut config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .entry("settings".to_string())
+                .or_default()
+                .insert("policy".to_string(), source.clone());
+        }
+        ConfigManager::save_config();
+        println!(
+            "{} `cu standards` now checks against {}",
+            "[SUCCESS]".green(),
+            policy.name.unwrap_or(source)
+        );
+    }
+
+    /// Removes `settings.policy`, falling back to a policy file in the project root if any
+    pub fn remove_policy() {
+        ConfigManager::load_config();
+        let removed = {
+            let mut config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .get_mut("settings")
+                .and_then(|settings| settings.remove("policy"))
+        };
+        match removed {
+            Some(source) => {
+                ConfigManager::save_config();
+                println!("{} Removed the policy {}", "[SUCCESS]".green(), source);
+            }
+            None => println!("{} No policy is configured.", "[INFO]".yellow()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Creates a project with the given files under the system temp directory
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("curator-policy-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (path, text) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+        root
+    }
+
+    fn check<'a>(report: &'a StandardsReport, id: &str) -> &'a StandardCheck {
+        report
+            .checks
+            .iter()
+            .find(|check| check.id == id)
+            .unwrap_or_else(|| panic!("no check {id}"))
+    }
+
+    #[test]
+    fn toml_and_json_policies_parse_the_same() {
+        let toml: Policy = toml::from_str(
+            r#"
+name = "Acme"
+
+[files]
+required = ["readme", "docs/*.md"]
+optional = ["security"]
+
+[licenses]
+allowed = ["MIT", "Apache-2.0"]
+
+[[headers]]
+id = "spdx"
+include = ["src/**/*.rs"]
+text = "SPDX-License-Identifier: {license}"
+"#,
+        )
+        .unwrap();
+        let json: Policy = serde_json::from_str(
+            r#"{
+                "name": "Acme",
+                "files": { "required": ["readme", "docs/*.md"], "optional": ["security"] },
+                "licenses": { "allowed": ["MIT", "Apache-2.0"] },
+                "headers": [{
+                    "id": "spdx",
+                    "include": ["src/**/*.rs"],
+                    "text": "SPDX-License-Identifier: {license}"
+                }]
+            }"#,
+        )
+        .unwrap();
+        for policy in [toml, json] {
+            assert_eq!(policy.name.as_deref(), Some("Acme"));
+            assert_eq!(policy.files.required, ["readme", "docs/*.md"]);
+            assert_eq!(policy.files.optional, ["security"]);
+            assert_eq!(policy.licenses.allowed, ["MIT", "Apache-2.0"]);
+            assert_eq!(policy.headers[0].lines, 10);
+            assert!(policy.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(toml::from_str::<Policy>("[files]\nrequire = [\"readme\"]\n").is_err());
+        assert!(serde_json::from_str::<Policy>(r#"{ "licences": {} }"#).is_err());
+    }
+
+    #[test]
+    fn header_ids_come_from_the_id_or_name() {
+        let rule = |id: Option<&str>, name: Option<&str>| HeaderRule {
+            id: id.map(str::to_string),
+            name: name.map(str::to_string),
+            include: vec!["*.rs".to_string()],
+            exclude: Vec::new(),
+            text: "Copyright".to_string(),
+            lines: 10,
+        };
+        assert_eq!(
+            rule(Some("spdx"), Some("SPDX header"))
+                .check_id()
+                .as_deref(),
+            Some("header-spdx")
+        );
+        assert_eq!(
+            rule(None, Some("Copyright Notice")).check_id().as_deref(),
+            Some("header-copyright-notice")
+        );
+        assert_eq!(rule(None, None).check_id(), None);
+
+        let mut policy = Policy {
+            headers: vec![rule(None, None)],
+            ..Policy::default()
+        };
+        assert!(policy.validate().is_err());
+        policy.headers = vec![rule(Some("a"), None), rule(None, Some("A"))];
+        assert!(policy.validate().unwrap_err().contains("header-a"));
+    }
+
+    #[test]
+    fn evaluate_checks_files_and_headers() {
+        let root = project(
+            "files",
+            &[
+                ("README.md", "# Demo\n"),
+                ("docs/guide.md", "Guide\n"),
+                ("src/lib.rs", "// Copyright Acme\npub fn a() {}\n"),
+                ("src/main.rs", "fn main() {}\n"),
+                ("src/generated.rs", "pub fn b() {}\n"),
+            ],
+        );
+        let policy: Policy = toml::from_str(
+            r#"
+[files]
+required = ["readme", "docs/*.md", "NOTICE"]
+optional = ["security"]
+
+[[headers]]
+name = "Copyright"
+include = ["src/*.rs"]
+exclude = ["src/generated.rs"]
+text = "Copyright Acme"
+"#,
+        )
+        .unwrap();
+        let report = policy.evaluate(&root);
+
+        assert!(check(&report, "readme").passed);
+        assert_eq!(
+            check(&report, "docs/*.md").path.as_deref(),
+            Some("docs/guide.md")
+        );
+        assert!(!check(&report, "NOTICE").passed);
+        let security = check(&report, "security");
+        assert!(!security.passed && !security.required);
+        let header = check(&report, "header-copyright");
+        assert!(!header.passed);
+        assert_eq!(header.files, ["src/main.rs"]);
+
+        // README (3) and docs/*.md (1) pass, NOTICE (1) and the header (2) fail
+        assert_eq!((report.score, report.max_score), (4, 7));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn evaluate_checks_the_configured_license() {
+        let root = project("license", &[("LICENSE", "MIT License\n")]);
+        CONFIGURATION
+            .lock()
+            .unwrap()
+            .entry("data".to_string())
+            .or_default()
+            .insert("license".to_string(), "MIT".to_string());
+        let policy = |allowed: &[&str]| Policy {
+            files: FileRules {
+                required: vec!["license".to_string()],
+                optional: Vec::new(),
+            },
+            licenses: LicenseRules {
+                allowed: allowed.iter().map(|id| id.to_string()).collect(),
+            },
+            ..Policy::default()
+        };
+
+        let report = policy(&["mit", "Apache-2.0"]).evaluate(&root);
+        assert!(check(&report, "allowed-license").passed);
+        assert_eq!(report.percent, 100);
+
+        let report = policy(&["Apache-2.0"]).evaluate(&root);
+        let license = check(&report, "allowed-license");
+        assert!(!license.passed);
+        assert_eq!(license.files, ["LICENSE"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn evaluate_identifies_the_conduct_file_over_the_configured_id() {
+        CONFIGURATION
+            .lock()
+            .unwrap()
+            .entry("data".to_string())
+            .or_default()
+            .insert("conduct".to_string(), "contributor-covenant".to_string());
+        let policy = Policy {
+            files: FileRules {
+                required: vec!["readme".to_string()],
+                optional: Vec::new(),
+            },
+            conduct: ConductRules {
+                required: Some("contributor-covenant".to_string()),
+            },
+            ..Policy::default()
+        };
+
+        let root = project(
+            "conduct-other",
+            &[(
+                "CODE_OF_CONDUCT.md",
+                include_str!("../templates/conduct/django.md"),
+            )],
+        );
+        let report = policy.evaluate(&root);
+        let conduct = check(&report, "required-conduct");
+        assert!(!conduct.passed);
+        assert!(
+            conduct.explanation.ends_with("found django."),
+            "{}",
+            conduct.explanation
+        );
+        assert_eq!(conduct.files, ["CODE_OF_CONDUCT.md"]);
+        fs::remove_dir_all(root).unwrap();
+
+        let root = project(
+            "conduct-required",
+            &[(
+                ".github/CODE_OF_CONDUCT.md",
+                include_str!("../templates/conduct/contributor-covenant-2.1.md"),
+            )],
+        );
+        let report = policy.evaluate(&root);
+        assert!(check(&report, "required-conduct").passed);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
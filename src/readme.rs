@@ -0,0 +1,679 @@
+use crossterm::style::Stylize;
+use dialoguer::Input;
+use std::path::Path;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::ecosystem::Ecosystem;
+use crate::git::Git;
+use crate::standards::StandardsReport;
+use crate::tools::Tools;
+
+/// Sections every README should have, with the words that satisfy them
+const REQUIRED_SECTIONS: &[(&str, &[&str])] = &[
+    ("Installation", &["install", "getting started", "setup"]),
+    (
+        "Usage",
+        &["usage", "example", "quick start", "getting started"],
+    ),
+    ("Contributing", &["contribut"]),
+    ("License", &["license", "licence"]),
+];
+
+/// Something `cu readme lint` found wrong with the README
+#[derive(Clone, Debug)]
+pub struct ReadmeIssue {
+    pub rule: &'static str,
+    /// 1-based line the issue is on, if it belongs to one
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+pub struct ReadmeManager;
+
+impl ReadmeManager {
+    /// Creates `README.md` with sections for the detected ecosystem and badges
+    pub fn init_readme(force: bool) {
+        if Path::new("README.md").exists() && !force {
+            eprintln!("{} README.md already exists.", "[ERROR]".red());
+            println!(
+                "{} Run `{}` to replace it",
+                "[FIX]".green(),
+                "cu readme init --force".grey()
+            );
+            process::exit(1);
+        }
+        if let Err(e) = Self::generate(true) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+
+    /// Generates `README.md`, asking for the description when `interactive`
+    pub fn generate(interactive: bool) -> Result<(), String> {
+        ConfigManager::load_config();
+        let root = Path::new(".");
+        let ecosystems = Ecosystem::detect(root);
+        let (project, license) = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            (
+                config_guard
+                    .get("settings")
+                    .and_then(|settings| settings.get("project"))
+                    .cloned()
+                    .unwrap_or_else(|| "Project".to_string()),
+                config_guard
+                    .get("data")
+                    .and_then(|data| data.get("license"))
+                    .cloned(),
+            )
+        };
+        let (package, description) = ecosystems
+            .first()
+            .map(|ecosystem| ecosystem.metadata(root))
+            .unwrap_or_default();
+        let package = package.unwrap_or_else(|| project.clone());
+        let description =
+            description.unwrap_or_else(|| format!("A short description of {project}."));
+        let description = if interactive {
+            Input::<String>::new()
+                .with_prompt("Description")
+                .default(description)
+                .interact_text()
+                .unwrap()
+        } else {
+            description
+        };
+        let repository = Git::web_url().or_else(Git::remote_url).or_else(|| {
+            if !interactive {
+                return None;
+            }
+            let url = Input::<String>::new()
+                .with_prompt("Repository URL (optional)")
+                .allow_empty(true)
+                .interact_text()
+                .unwrap();
+            let url = url.trim();
+            (!url.is_empty()).then(|| url.to_string())
+        });
+
+        let text = include_str!("../templates/community/readme.md")
+            .replace("{{title}}", &project)
+            .replace("{{badges}}", &Self::badges(root, license.as_deref()))
+            .replace("{{description}}", &description)
+            .replace(
+                "{{installation}}",
+                &Self::installation(root, &ecosystems, &package, repository.as_deref()),
+            )
+            .replace("{{usage}}", &Self::usage(root, &ecosystems, &package))
+            .replace("{{contributing}}", &Self::contributing(root, &ecosystems))
+            .replace("{{license}}", &Self::license(root, license.as_deref()));
+        Tools::write_file("README.md", &text)?;
+        println!("{} Created README.md", "[SUCCESS]".green());
+        Ok(())
+    }
+
+    /// Builds license and CI badges from `curator.json` and the `origin` remote
+    pub fn badges(root: &Path, license: Option<&str>) -> String {
+        let mut badges = Vec::new();
+        if let Some(license) = license {
+            let label = license
+                .replace('-', "--")
+                .replace('_', "__")
+                .replace(' ', "%20");
+            badges.push(format!(
+                "[![License: {license}](https://img.shields.io/badge/license-{label}-blue.svg)](LICENSE)"
+            ));
+        }
+        if let Some(web) = Git::web_url() {
+            let branch = Git::default_branch().unwrap_or_else(|| "main".to_string());
+            if web.starts_with("https://github.com/") {
+                if let Some(workflow) = Self::workflow(root) {
+                    badges.push(format!(
+                        "[![CI]({web}/actions/workflows/{workflow}/badge.svg)]({web}/actions/workflows/{workflow})"
+                    ));
+                }
+            } else if web.contains("gitlab") && root.join(".gitlab-ci.yml").is_file() {
+                badges.push(format!(
+                    "[![pipeline]({web}/badges/{branch}/pipeline.svg)]({web}/-/commits/{branch})"
+                ));
+            }
+        }
+        if badges.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n\n", badges.join("\n"))
+        }
+    }
+
+    /// Picks the GitHub Actions workflow shown in the CI badge, preferring `ci.yml`
+    fn workflow(root: &Path) -> Option<String> {
+        let mut workflows: Vec<String> = std::fs::read_dir(root.join(".github/workflows"))
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".yml") || name.ends_with(".yaml"))
+            .collect();
+        workflows.sort();
+        workflows
+            .iter()
+            .find(|name| name.starts_with("ci."))
+            .or_else(|| workflows.first())
+            .cloned()
+    }
+
+    /// Builds install commands, with clone steps only when the repository URL is known
+    fn installation(
+        root: &Path,
+        ecosystems: &[Ecosystem],
+        package: &str,
+        repository: Option<&str>,
+    ) -> String {
+        let clone = repository.map(|url| {
+            let url = url.trim_end_matches('/').trim_end_matches(".git");
+            let dir = url.rsplit(['/', ':']).next().unwrap_or(url);
+            format!("git clone {url}.git\ncd {dir}")
+        });
+        let Some(ecosystem) = ecosystems.first() else {
+            return match clone {
+                Some(clone) => format!("```bash\n{clone}\n```\n"),
+                None => "Describe how to install the project.\n".to_string(),
+            };
+        };
+        let install = match ecosystem {
+            Ecosystem::Rust if root.join("src/main.rs").is_file() => {
+                format!("cargo install {package}")
+            }
+            Ecosystem::Rust => format!("cargo add {package}"),
+            Ecosystem::Node => format!("npm install {package}"),
+            Ecosystem::Python => format!("pip install {package}"),
+            Ecosystem::Go => format!("go get {package}"),
+        };
+        let Some(clone) = clone else {
+            return format!("```bash\n{install}\n```\n");
+        };
+        let mut section =
+            format!("```bash\n{install}\n```\n\nOr build it from source:\n\n```bash\n{clone}\n");
+        if let Some(build) = ecosystem.commands(root).build {
+            section.push_str(&format!("{build}\n"));
+        }
+        section.push_str("```\n");
+        section
+    }
+
+    fn usage(root: &Path, ecosystems: &[Ecosystem], package: &str) -> String {
+        let example = match ecosystems.first() {
+            Some(Ecosystem::Rust) if root.join("src/main.rs").is_file() => {
+                format!("```bash\n{package} --help\n```\n")
+            }
+            Some(Ecosystem::Rust) => format!("```rust\nuse {};\n```\n", package.replace('-', "_")),
+            Some(Ecosystem::Node) => format!(
+                "```js\nconst {} = require(\"{package}\");\n```\n",
+                Self::identifier(package)
+            ),
+            Some(Ecosystem::Python) => format!(
+                "```python\nimport {}\n```\n",
+                package.replace('-', "_").to_lowercase()
+            ),
+            Some(Ecosystem::Go) => format!("```go\nimport \"{package}\"\n```\n"),
+            None => String::new(),
+        };
+        if example.is_empty() {
+            "Describe how to use the project.\n".to_string()
+        } else {
+            format!("{example}\nDescribe how to use the project.\n")
+        }
+    }
+
+    /// Turns a package name like `@scope/my-lib` into `myLib`
+    fn identifier(package: &str) -> String {
+        let name = package.rsplit('/').next().unwrap_or(package);
+        let mut identifier = String::new();
+        let mut upper = false;
+        for c in name.chars() {
+            if c.is_alphanumeric() {
+                if upper && !identifier.is_empty() {
+                    identifier.extend(c.to_uppercase());
+                } else {
+                    identifier.push(c);
+                }
+                upper = false;
+            } else {
+                upper = true;
+            }
+        }
+        identifier
+    }
+
+    fn contributing(root: &Path, ecosystems: &[Ecosystem]) -> String {
+        let report = StandardsReport::evaluate(root);
+        let guide = report
+            .checks
+            .iter()
+            .find(|check| check.id == "contributing")
+            .and_then(|check| check.path.clone());
+        if let Some(path) = guide {
+            return format!(
+                "Contributions are welcome! See [{path}]({path}) for how to get started.\n"
+            );
+        }
+        let mut section =
+            String::from("Contributions are welcome! Please open an issue or pull request.\n");
+        if let Some(ecosystem) = ecosystems.first() {
+            let commands = ecosystem.commands(root);
+            let steps: Vec<String> = [commands.build, commands.test, commands.lint]
+                .into_iter()
+                .flatten()
+                .collect();
+            if !steps.is_empty() {
+                section.push_str(&format!("\n```bash\n{}\n```\n", steps.join("\n")));
+            }
+        }
+        section
+    }
+
+    fn license(root: &Path, license: Option<&str>) -> String {
+        let path = StandardsReport::evaluate(root)
+            .checks
+            .into_iter()
+            .find(|check| check.id == "license")
+            .and_then(|check| check.path)
+            .unwrap_or_else(|| "LICENSE".to_string());
+        match license {
+            Some(license) => format!(
+                "This project is licensed under the {license} license. See [{path}]({path}) for details.\n"
+            ),
+            None => format!("See [{path}]({path}) for details.\n"),
+        }
+    }
+
+    /// Checks the README for required sections, the license, broken links and foreign badges
+    pub fn lint_readme() {
+        ConfigManager::load_config();
+        let root = Path::new(".");
+        let path = StandardsReport::evaluate(root)
+            .checks
+            .into_iter()
+            .find(|check| check.id == "readme")
+            .and_then(|check| check.path);
+        let Some(path) = path else {
+            eprintln!("{} No README found.", "[ERROR]".red());
+            println!(
+                "{} Run `{}` to create one",
+                "[FIX]".green(),
+                "cu readme init".grey()
+            );
+            process::exit(1);
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{} Failed to read {}: {}", "[ERROR]".red(), path, e);
+                process::exit(1);
+            }
+        };
+        let license = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .get("data")
+                .and_then(|data| data.get("license"))
+                .cloned()
+        };
+        let repository = Git::web_url().and_then(|url| Self::repository_slug(&url));
+        let base = Path::new(&path).parent().unwrap_or(root);
+        let issues = Self::lint(&text, base, license.as_deref(), repository.as_deref());
+
+        if issues.is_empty() {
+            println!("{} {} passed all checks", "[SUCCESS]".green(), path);
+            return;
+        }
+        for issue in &issues {
+            let location = match issue.line {
+                Some(line) => format!("{path}:{line}"),
+                None => path.clone(),
+            };
+            println!(
+                "{} {} {} {}",
+                "✗".red(),
+                location,
+                issue.message,
+                format!("[{}]", issue.rule).grey()
+            );
+        }
+        eprintln!(
+            "{} Found {} problem{} in {}",
+            "[ERROR]".red(),
+            issues.len(),
+            if issues.len() == 1 { "" } else { "s" },
+            path
+        );
+        process::exit(1);
+    }
+
+    /// Lints README text whose relative links resolve from `base`
+    ///
+    /// `repository` is the `owner/name` slug badges are expected to point at.
+    pub fn lint(
+        text: &str,
+        base: &Path,
+        license: Option<&str>,
+        repository: Option<&str>,
+    ) -> Vec<ReadmeIssue> {
+        let lines = Self::prose_lines(text);
+        let headings: Vec<(usize, usize, String)> = lines
+            .iter()
+            .filter_map(|(i, line)| {
+                let level = line.chars().take_while(|&c| c == '#').count();
+                let title = line[level..].strip_prefix(' ')?;
+                (1..=6).contains(&level).then(|| {
+                    (
+                        *i,
+                        level,
+                        title.trim().trim_end_matches('#').trim().to_lowercase(),
+                    )
+                })
+            })
+            .collect();
+
+        let mut issues = Vec::new();
+        for (section, words) in REQUIRED_SECTIONS {
+            if !headings
+                .iter()
+                .any(|(_, _, title)| words.iter().any(|word| title.contains(word)))
+            {
+                issues.push(ReadmeIssue {
+                    rule: "required-section",
+                    line: None,
+                    message: format!("Missing a {section} section"),
+                });
+            }
+        }
+
+        if let Some(license) = license {
+            let section = headings
+                .iter()
+                .enumerate()
+                .find(|(_, (_, _, title))| title.contains("license") || title.contains("licence"));
+            if let Some((index, (start, level, _))) = section {
+                let end = headings[index + 1..]
+                    .iter()
+                    .find(|(_, next_level, _)| next_level <= level)
+                    .map_or(usize::MAX, |(line, _, _)| *line);
+                let body: String = lines
+                    .iter()
+                    .filter(|(i, _)| i > start && *i < end)
+                    .map(|(_, line)| line.to_lowercase() + "\n")
+                    .collect();
+                if !Self::mentions_license(&body, license) {
+                    issues.push(ReadmeIssue {
+                        rule: "license-mismatch",
+                        line: Some(start + 1),
+                        message: format!(
+                            "The License section does not mention {license}, the license in curator.json"
+                        ),
+                    });
+                }
+            }
+        }
+
+        for (i, line) in &lines {
+            for (target, image) in Self::links(line) {
+                if let Some(missing) = Self::unresolved(base, &target) {
+                    issues.push(ReadmeIssue {
+                        rule: "broken-link",
+                        line: Some(i + 1),
+                        message: format!(
+                            "{} `{}` does not exist",
+                            if image { "Image" } else { "Link" },
+                            missing
+                        ),
+                    });
+                }
+                let is_badge = image
+                    && (target.contains("badge")
+                        || target.contains("shields.io")
+                        || target.contains("/workflows/"));
+                if let (true, Some(repository)) = (is_badge, repository) {
+                    if let Some(slug) = Self::repository_slug(&target) {
+                        if !slug.eq_ignore_ascii_case(repository) {
+                            issues.push(ReadmeIssue {
+                                rule: "foreign-badge",
+                                line: Some(i + 1),
+                                message: format!("Badge points at {slug} instead of {repository}"),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        issues
+    }
+
+    /// Returns the lines outside fenced code blocks with their index, without inline code
+    fn prose_lines(text: &str) -> Vec<(usize, String)> {
+        let mut fence: Option<String> = None;
+        let mut lines = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim_start();
+            let marker = ["```", "~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker));
+            match (&fence, marker) {
+                (None, Some(marker)) => fence = Some(marker.to_string()),
+                (Some(open), Some(marker)) if open == marker => fence = None,
+                (None, None) => {
+                    let mut prose = String::new();
+                    for (j, part) in line.split('`').enumerate() {
+                        if j % 2 == 0 {
+                            prose.push_str(part);
+                        }
+                    }
+                    lines.push((i, prose));
+                }
+                _ => {}
+            }
+        }
+        lines
+    }
+
+    /// Extracts link and image targets from a line, flagging images
+    fn links(line: &str) -> Vec<(String, bool)> {
+        let mut links = Vec::new();
+        let mut rest = line;
+        while let Some(start) = rest.find("](") {
+            let image = rest[..start]
+                .rfind('[')
+                .is_some_and(|open| rest[..open].ends_with('!'));
+            let after = &rest[start + 2..];
+            let Some(end) = after.find(')') else {
+                break;
+            };
+            let target = after[..end].trim();
+            let target = target
+                .split_once(" \"")
+                .map_or(target, |(target, _)| target)
+                .trim_matches(['<', '>']);
+            links.push((target.to_string(), image));
+            rest = &after[end..];
+        }
+        for (attribute, image) in [("src=\"", true), ("href=\"", false)] {
+            let mut rest = line;
+            while let Some(start) = rest.find(attribute) {
+                let after = &rest[start + attribute.len()..];
+                let Some(end) = after.find('"') else {
+                    break;
+                };
+                links.push((after[..end].to_string(), image));
+                rest = &after[end..];
+            }
+        }
+        if let Some(definition) = line.trim_start().strip_prefix('[') {
+            if let Some((_, target)) = definition.split_once("]:") {
+                if let Some(target) = target.split_whitespace().next() {
+                    links.push((target.trim_matches(['<', '>']).to_string(), false));
+                }
+            }
+        }
+        links
+    }
+
+    /// Returns the path of a relative link that does not resolve to a file in the repository
+    fn unresolved(base: &Path, target: &str) -> Option<String> {
+        if target.is_empty()
+            || target.starts_with('#')
+            || target.contains("://")
+            || target.starts_with("mailto:")
+            || target.starts_with("data:")
+            || target.starts_with("//")
+        {
+            return None;
+        }
+        let path = target.split(['#', '?']).next().unwrap_or(target);
+        let path = path.replace("%20", " ");
+        let resolved = match path.strip_prefix('/') {
+            Some(path) => Path::new(".").join(path),
+            None => base.join(&path),
+        };
+        (!resolved.exists()).then_some(path)
+    }
+
+    /// Checks whether a section names a license by its id or the words and numbers in it
+    fn mentions_license(section: &str, license: &str) -> bool {
+        let license = license.to_lowercase();
+        if section.contains(&license) {
+            return true;
+        }
+        license
+            .split(['-', ' '])
+            .filter(|part| !part.is_empty() && *part != "only" && *part != "or" && *part != "later")
+            .all(|part| section.contains(part))
+    }
+
+    /// Extracts `owner/name` from a forge, shields.io or codecov URL
+    pub fn repository_slug(url: &str) -> Option<String> {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let (host, path) = rest.split_once('/')?;
+        let parts: Vec<&str> = path
+            .split(['/', '?', '#'])
+            .filter(|part| !part.is_empty())
+            .collect();
+        let skip = match host {
+            "github.com" | "gitlab.com" | "www.github.com" => 0,
+            // e.g. /github/license/o/r, /github/v/release/o/r, /github/actions/workflow/status/o/r/ci.yml
+            "img.shields.io" => match parts.as_slice() {
+                ["github", "actions", "workflow", "status", ..] => 4,
+                ["github", "v", ..] => 3,
+                ["github" | "gitlab", ..] => 2,
+                _ => return None,
+            },
+            // e.g. /gh/o/r
+            "codecov.io" | "app.codecov.io" => 1,
+            _ => return None,
+        };
+        let mut parts = parts.into_iter().skip(skip);
+        let owner = parts.next()?;
+        let name = parts.next()?.trim_end_matches(".git");
+        Some(format!("{owner}/{name}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "# Demo
+
+[![CI](https://github.com/acme/demo/actions/workflows/ci.yml/badge.svg)](https://github.com/acme/demo/actions)
+[![License: MIT](https://img.shields.io/badge/license-MIT-blue.svg)](LICENSE)
+
+## Installation
+
+```bash
+cargo install demo
+```
+
+## Usage
+
+See [the sources](src/lib.rs) and [usage](#usage).
+
+## Contributing
+
+Read [CONTRIBUTING](CONTRIBUTING.MD).
+
+## License
+
+Released under the MIT License.
+";
+
+    fn lint(text: &str, license: Option<&str>) -> Vec<(&'static str, Option<usize>)> {
+        ReadmeManager::lint(
+            text,
+            Path::new(env!("CARGO_MANIFEST_DIR")),
+            license,
+            Some("acme/demo"),
+        )
+        .into_iter()
+        .map(|issue| (issue.rule, issue.line))
+        .collect()
+    }
+
+    #[test]
+    fn complete_readme_has_no_issues() {
+        assert_eq!(lint(README, Some("MIT")), []);
+    }
+
+    #[test]
+    fn missing_sections_are_reported() {
+        let text = README.replace("## Installation", "## Setup");
+        assert_eq!(lint(&text, None), []);
+        let issues = ReadmeManager::lint(
+            "# Demo\n\n## Usage\n\nRun it.\n",
+            Path::new("."),
+            None,
+            None,
+        );
+        let messages: Vec<&str> = issues.iter().map(|issue| issue.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Missing a Installation section",
+                "Missing a Contributing section",
+                "Missing a License section"
+            ]
+        );
+    }
+
+    #[test]
+    fn license_section_must_name_the_configured_license() {
+        assert_eq!(
+            lint(README, Some("Apache-2.0")),
+            [("license-mismatch", Some(20))]
+        );
+        let text = README.replace("the MIT License", "the Apache License, Version 2.0");
+        assert_eq!(lint(&text, Some("Apache-2.0")), []);
+    }
+
+    #[test]
+    fn broken_relative_links_are_reported() {
+        let text = README.replace("src/lib.rs", "src/missing.rs")
+            + "\n`[ignored](nowhere.md)`\n\n```md\n![ignored](nowhere.png)\n```\n";
+        let issues = ReadmeManager::lint(&text, Path::new(env!("CARGO_MANIFEST_DIR")), None, None);
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].rule, issues[0].line), ("broken-link", Some(14)));
+        assert_eq!(issues[0].message, "Link `src/missing.rs` does not exist");
+    }
+
+    #[test]
+    fn badges_for_other_repositories_are_reported() {
+        let text = README.replace(
+            "github.com/acme/demo/actions/workflows",
+            "github.com/someone/else/actions/workflows",
+        );
+        assert_eq!(lint(&text, Some("MIT")), [("foreign-badge", Some(3))]);
+        assert_eq!(
+            ReadmeManager::repository_slug("https://img.shields.io/github/v/release/acme/demo")
+                .as_deref(),
+            Some("acme/demo")
+        );
+    }
+}
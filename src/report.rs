@@ -0,0 +1,365 @@
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::standards::{StandardCheck, StandardsReport};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Machine-readable formats for CI dashboards
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// SARIF 2.1.0, read by code scanning tools
+    Sarif,
+    /// JUnit XML, read by most CI test result views
+    Junit,
+}
+
+impl ReportFormat {
+    pub const ALL: &'static [ReportFormat] = &[ReportFormat::Sarif, ReportFormat::Junit];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            ReportFormat::Sarif => "sarif",
+            ReportFormat::Junit => "junit",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<ReportFormat> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| format.id() == id.to_lowercase())
+    }
+
+    /// Guesses the format from a report file name, such as `out.sarif` or `junit.xml`
+    pub fn from_path(path: &str) -> Option<ReportFormat> {
+        let extension = Path::new(path)
+            .extension()?
+            .to_string_lossy()
+            .to_lowercase();
+        match extension.as_str() {
+            "sarif" | "json" => Some(ReportFormat::Sarif),
+            "xml" => Some(ReportFormat::Junit),
+            _ => None,
+        }
+    }
+
+    /// Renders a standards report in this format
+    pub fn render(&self, report: &StandardsReport) -> String {
+        match self {
+            ReportFormat::Sarif => {
+                serde_json::to_string_pretty(&Self::sarif(report)).unwrap_or_default() + "\n"
+            }
+            ReportFormat::Junit => Self::junit(report),
+        }
+    }
+
+    /// Builds a SARIF log with one rule per check and one result per failing file
+    ///
+    /// Missing files have no file to point at, so their results name the check as a logical
+    /// location instead.
+    fn sarif(report: &StandardsReport) -> Value {
+        let rules: Vec<Value> = report
+            .checks
+            .iter()
+            .map(|check| {
+                json!({
+                    "id": check.id,
+                    "name": check.title,
+                    "shortDescription": { "text": check.title },
+                    "fullDescription": { "text": check.explanation },
+                    "defaultConfiguration": { "level": Self::level(check) },
+                    "properties": { "weight": check.weight, "required": check.required },
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for (index, check) in report.checks.iter().enumerate() {
+            if check.passed {
+                continue;
+            }
+            let result = |location: Value, text: String| {
+                json!({
+                    "ruleId": check.id,
+                    "ruleIndex": index,
+                    "level": Self::level(check),
+                    "message": { "text": text },
+                    "locations": [location],
+                })
+            };
+            if check.files.is_empty() {
+                let location = json!({
+                    "logicalLocations": [{
+                        "name": check.id,
+                        "fullyQualifiedName": format!("standards.{}", check.id),
+                        "kind": "module",
+                    }],
+                });
+                results.push(result(
+                    location,
+                    format!(
+                        "{} is missing. {} Looked for: {}",
+                        check.title,
+                        check.explanation,
+                        check.locations.join(", ")
+                    ),
+                ));
+            } else {
+                for file in &check.files {
+                    let location = json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": file, "uriBaseId": "%SRCROOT%" },
+                            "region": { "startLine": 1 },
+                        },
+                    });
+                    results.push(result(
+                        location,
+                        format!("{} failed. {}", check.title, check.explanation),
+                    ));
+                }
+            }
+        }
+
+        let mut driver = json!({
+            "name": "curator",
+            "version": env!("CARGO_PKG_VERSION"),
+            "informationUri": env!("CARGO_PKG_REPOSITORY"),
+            "rules": rules,
+        });
+        if let Some(policy) = &report.policy {
+            driver["properties"] = json!({ "policy": policy });
+        }
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": driver },
+                "originalUriBaseIds": { "%SRCROOT%": { "uri": "./" } },
+                "invocations": [{
+                    "executionSuccessful": true,
+                    "toolExecutionNotifications": report
+                        .warnings
+                        .iter()
+                        .map(|warning| json!({ "level": "warning", "message": { "text": warning } }))
+                        .collect::<Vec<Value>>(),
+                }],
+                "results": results,
+                "properties": {
+                    "score": report.score,
+                    "maxScore": report.max_score,
+                    "percent": report.percent,
+                },
+            }],
+        })
+    }
+
+    /// Builds a JUnit test suite with one test case per check
+    ///
+    /// Missing optional files are reported as skipped rather than failed.
+    fn junit(report: &StandardsReport) -> String {
+        let suite = report
+            .policy
+            .as_deref()
+            .map(|policy| format!("curator standards ({policy})"))
+            .unwrap_or_else(|| "curator standards".to_string());
+        let failures = report
+            .checks
+            .iter()
+            .filter(|check| check.required && !check.passed)
+            .count();
+        let skipped = report
+            .checks
+            .iter()
+            .filter(|check| !check.required && !check.passed)
+            .count();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"curator\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\">\n",
+            report.checks.len()
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\" errors=\"0\">\n",
+            Self::escape(&suite),
+            report.checks.len()
+        ));
+        xml.push_str(&format!(
+            "    <properties>\n      <property name=\"score\" value=\"{}\"/>\n      <property name=\"max-score\" value=\"{}\"/>\n    </properties>\n",
+            report.score, report.max_score
+        ));
+        for check in &report.checks {
+            xml.push_str(&Self::testcase(check));
+        }
+        if !report.warnings.is_empty() {
+            xml.push_str(&format!(
+                "    <system-out>{}</system-out>\n",
+                Self::escape(&report.warnings.join("\n"))
+            ));
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    fn testcase(check: &StandardCheck) -> String {
+        let file = check
+            .path
+            .as_ref()
+            .or(check.files.first())
+            .map(|file| format!(" file=\"{}\"", Self::escape(file)))
+            .unwrap_or_default();
+        let open = format!(
+            "    <testcase classname=\"standards.{}\" name=\"{}\"{file}",
+            Self::escape(&check.id),
+            Self::escape(&check.title)
+        );
+        if check.passed {
+            return format!("{open}/>\n");
+        }
+        if !check.required {
+            return format!(
+                "{open}>\n      <skipped message=\"Optional: {}\"/>\n    </testcase>\n",
+                Self::escape(&check.explanation)
+            );
+        }
+        let details = if check.files.is_empty() {
+            format!("Looked for: {}", check.locations.join(", "))
+        } else {
+            check.files.join("\n")
+        };
+        format!(
+            "{open}>\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>\n",
+            Self::escape(&check.id),
+            Self::escape(&check.explanation),
+            Self::escape(&details)
+        )
+    }
+
+    /// Maps a check to a SARIF level, so optional checks do not fail code scanning gates
+    fn level(check: &StandardCheck) -> &'static str {
+        if check.required {
+            "error"
+        } else {
+            "note"
+        }
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(id: &str, passed: bool, required: bool, files: &[&str]) -> StandardCheck {
+        StandardCheck {
+            id: id.to_string(),
+            title: id.to_uppercase(),
+            passed,
+            path: passed.then(|| format!("{}.md", id.to_uppercase())),
+            weight: 2,
+            explanation: format!("Explains <{id}> & more."),
+            locations: vec![".github/".to_string(), "docs/".to_string()],
+            required,
+            files: files.iter().map(|file| file.to_string()).collect(),
+        }
+    }
+
+    fn report() -> StandardsReport {
+        let mut report = StandardsReport::from_checks(vec![
+            check("readme", true, true, &[]),
+            check("security", false, true, &[]),
+            check("funding", false, false, &[]),
+            check("header-spdx", false, true, &["src/a.rs", "src/b.rs"]),
+        ]);
+        report.policy = Some("Acme".to_string());
+        report
+            .warnings
+            .push("security.txt expires soon".to_string());
+        report
+    }
+
+    #[test]
+    fn formats_are_found_by_id_and_extension() {
+        assert_eq!(ReportFormat::from_id("SARIF"), Some(ReportFormat::Sarif));
+        assert_eq!(ReportFormat::from_id("junit"), Some(ReportFormat::Junit));
+        assert_eq!(ReportFormat::from_id("html"), None);
+        assert_eq!(
+            ReportFormat::from_path("out/standards.sarif"),
+            Some(ReportFormat::Sarif)
+        );
+        assert_eq!(
+            ReportFormat::from_path("junit.XML"),
+            Some(ReportFormat::Junit)
+        );
+        assert_eq!(ReportFormat::from_path("report"), None);
+    }
+
+    #[test]
+    fn sarif_has_a_rule_per_check_and_a_result_per_failure() {
+        let sarif: Value = serde_json::from_str(&ReportFormat::Sarif.render(&report())).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 4);
+        assert_eq!(run["tool"]["driver"]["properties"]["policy"], "Acme");
+
+        let results = run["results"].as_array().unwrap();
+        let summary: Vec<(&str, &str, Option<&str>, Option<&str>)> = results
+            .iter()
+            .map(|result| {
+                let location = &result["locations"][0];
+                (
+                    result["ruleId"].as_str().unwrap(),
+                    result["level"].as_str().unwrap(),
+                    location["physicalLocation"]["artifactLocation"]["uri"].as_str(),
+                    location["logicalLocations"][0]["fullyQualifiedName"].as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("security", "error", None, Some("standards.security")),
+                ("funding", "note", None, Some("standards.funding")),
+                ("header-spdx", "error", Some("src/a.rs"), None),
+                ("header-spdx", "error", Some("src/b.rs"), None),
+            ]
+        );
+        assert!(results[0]["message"]["text"]
+            .as_str()
+            .unwrap()
+            .ends_with("Looked for: .github/, docs/"));
+        assert_eq!(results[2]["ruleIndex"], 3);
+        assert_eq!(
+            run["invocations"][0]["toolExecutionNotifications"][0]["message"]["text"],
+            "security.txt expires soon"
+        );
+        assert_eq!(run["properties"]["maxScore"], 6);
+    }
+
+    #[test]
+    fn junit_fails_required_checks_and_skips_optional_ones() {
+        let xml = ReportFormat::Junit.render(&report());
+        assert!(xml.contains(
+            "<testsuite name=\"curator standards (Acme)\" tests=\"4\" failures=\"2\" skipped=\"1\" errors=\"0\">"
+        ));
+        assert!(xml.contains(
+            "<testcase classname=\"standards.readme\" name=\"README\" file=\"README.md\"/>"
+        ));
+        assert!(
+            xml.contains("<skipped message=\"Optional: Explains &lt;funding&gt; &amp; more.\"/>")
+        );
+        assert!(xml.contains(">Looked for: .github/, docs/</failure>"));
+        assert!(xml.contains(
+            "name=\"HEADER-SPDX\" file=\"src/a.rs\">\n      <failure type=\"header-spdx\""
+        ));
+        assert!(xml.contains(">src/a.rs\nsrc/b.rs</failure>"));
+        assert!(xml.contains("<system-out>security.txt expires soon</system-out>"));
+    }
+}
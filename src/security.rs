@@ -0,0 +1,287 @@
+use chrono::{DateTime, Duration, FixedOffset, SecondsFormat, Utc};
+use crossterm::style::Stylize;
+use std::path::Path;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::git::Git;
+use crate::tools::Tools;
+
+const VERSIONS_START: &str = "<!-- curator:supported-versions:start -->";
+const VERSIONS_END: &str = "<!-- curator:supported-versions:end -->";
+
+/// Minor release lines listed in the table before older ones are summarized
+const MAX_LISTED_LINES: usize = 5;
+
+/// Where RFC 9116 expects the security contact file
+pub const SECURITY_TXT: &str = ".well-known/security.txt";
+
+/// Days until `security.txt` expires unless `data.security-txt-expires-days` is set
+const DEFAULT_EXPIRES_DAYS: i64 = 180;
+
+/// Days before expiry at which `cu standards` starts warning
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+
+pub struct SecurityManager;
+
+impl SecurityManager {
+    /// Creates `SECURITY.md` with a contact, disclosure policy and supported versions
+    pub fn set_security() {
+        if let Err(e) = Self::generate(true) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+
+    /// Generates `SECURITY.md`, prompting for missing values when `interactive`
+    pub fn generate(interactive: bool) -> Result<(), String> {
+        ConfigManager::load_config();
+        let template = include_str!("../templates/community/security.md");
+        let text = Tools::fill_template(template, interactive)?
+            .replace("{{versions}}", &Self::versions_block());
+        Tools::write_file("SECURITY.md", &text)?;
+        println!("{} Created SECURITY.md", "[SUCCESS]".green());
+        Ok(())
+    }
+
+    /// Refreshes the supported versions table in `SECURITY.md` from the git tags
+    pub fn reload_security() {
+        ConfigManager::load_config();
+        let text = match std::fs::read_to_string("SECURITY.md") {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{} Failed to read SECURITY.md: {}", "[ERROR]".red(), e);
+                println!(
+                    "{} Run `{}` to create it",
+                    "[FIX]".green(),
+                    "cu security set".grey()
+                );
+                process::exit(1);
+            }
+        };
+        let (Some(start), Some(end)) = (text.find(VERSIONS_START), text.find(VERSIONS_END)) else {
+            eprintln!(
+                "{} SECURITY.md has no supported versions table managed by curator.",
+                "[ERROR]".red()
+            );
+            println!(
+                "{} Run `{}` to recreate it",
+                "[FIX]".green(),
+                "cu security set".grey()
+            );
+            process::exit(1);
+        };
+        let updated = format!(
+            "{}{}{}",
+            &text[..start],
+            Self::versions_block(),
+            &text[end + VERSIONS_END.len()..]
+        );
+        if let Err(e) = Tools::write_file("SECURITY.md", &updated) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+        println!(
+            "{} Updated the supported versions in SECURITY.md",
+            "[SUCCESS]".green()
+        );
+    }
+
+    /// Builds the supported versions table between its markers
+    fn versions_block() -> String {
+        format!("{VERSIONS_START}\n{}{VERSIONS_END}", Self::versions_table())
+    }
+
+    /// Lists minor release lines from the semver tags, marking the newest ones as supported
+    ///
+    /// The number of supported lines comes from `data.security-supported-lines` (default 1).
+    pub fn versions_table() -> String {
+        let supported_lines = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .get("data")
+                .and_then(|data| data.get("security-supported-lines"))
+                .and_then(|lines| lines.parse::<usize>().ok())
+                .unwrap_or(1)
+                .max(1)
+        };
+
+        let versions: Vec<(u64, u64, u64)> = Git::semver_tags()
+            .into_iter()
+            .map(|(_, version)| version)
+            .collect();
+        Self::lines_table(&versions, supported_lines)
+    }
+
+    /// Builds the table for versions sorted newest first, supporting the newest `supported_lines`
+    fn lines_table(versions: &[(u64, u64, u64)], supported_lines: usize) -> String {
+        let mut lines: Vec<(u64, u64)> = Vec::new();
+        for (major, minor, _) in versions {
+            if !lines.contains(&(*major, *minor)) {
+                lines.push((*major, *minor));
+            }
+        }
+        if lines.is_empty() {
+            return "No releases have been tagged yet. Security fixes are made on the default\nbranch only.\n".to_string();
+        }
+
+        let mut table =
+            String::from("| Version | Supported          |\n| ------- | ------------------ |\n");
+        for (i, (major, minor)) in lines.iter().take(MAX_LISTED_LINES).enumerate() {
+            let mark = if i < supported_lines {
+                ":white_check_mark:"
+            } else {
+                ":x:               "
+            };
+            table.push_str(&format!(
+                "| {:<7} | {mark} |\n",
+                format!("{major}.{minor}.x")
+            ));
+        }
+        if let Some((major, minor)) = lines.get(MAX_LISTED_LINES - 1) {
+            if lines.len() > MAX_LISTED_LINES {
+                table.push_str(&format!(
+                    "| {:<7} | :x:                |\n",
+                    format!("< {major}.{minor}")
+                ));
+            }
+        }
+        table
+    }
+
+    /// Creates `.well-known/security.txt` (RFC 9116) from `curator.json`
+    ///
+    /// Uses `security-email`, `security-policy`, `security-languages`, `security-encryption`
+    /// and `security-txt-expires-days` from the `data` section.
+    pub fn generate_txt(interactive: bool) {
+        ConfigManager::load_config();
+        let setting = |key: &str| {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .get("data")
+                .and_then(|data| data.get(key))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let days = setting("security-txt-expires-days")
+            .and_then(|days| days.parse::<i64>().ok())
+            .filter(|days| *days > 0)
+            .unwrap_or(DEFAULT_EXPIRES_DAYS);
+        let expires =
+            (Utc::now() + Duration::days(days)).to_rfc3339_opts(SecondsFormat::Secs, true);
+        let policy = setting("security-policy").or_else(|| {
+            Path::new("SECURITY.md")
+                .is_file()
+                .then(|| Git::file_url("SECURITY.md"))
+                .flatten()
+        });
+        let languages = setting("security-languages").unwrap_or_else(|| "en".to_string());
+
+        let mut template = String::from("Contact: mailto:<security-email>\n");
+        template.push_str(&format!("Expires: {expires}\n"));
+        if let Some(encryption) = setting("security-encryption") {
+            template.push_str(&format!("Encryption: {encryption}\n"));
+        }
+        if let Some(policy) = &policy {
+            template.push_str(&format!("Policy: {policy}\n"));
+        }
+        template.push_str(&format!("Preferred-Languages: {languages}\n"));
+
+        let text = match Tools::fill_template(&template, interactive) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                process::exit(1);
+            }
+        };
+        if let Err(e) = Tools::write_file(SECURITY_TXT, &text) {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+        println!(
+            "{} Created {} (expires {})",
+            "[SUCCESS]".green(),
+            SECURITY_TXT,
+            &expires[..10]
+        );
+        if policy.is_none() {
+            println!(
+                "{} No Policy field: SECURITY.md or an `origin` remote is missing. Set `data.security-policy` to a URL to add one.",
+                "[INFO]".yellow()
+            );
+        }
+    }
+
+    /// Reads the `Expires` field of the `security.txt` in `root`
+    pub fn txt_expiry(root: &Path) -> Option<DateTime<FixedOffset>> {
+        let text = std::fs::read_to_string(root.join(SECURITY_TXT))
+            .or_else(|_| std::fs::read_to_string(root.join("security.txt")))
+            .ok()?;
+        text.lines().find_map(|line| {
+            let (field, value) = line.split_once(':')?;
+            field
+                .trim()
+                .eq_ignore_ascii_case("expires")
+                .then(|| DateTime::parse_from_rfc3339(value.trim()).ok())
+                .flatten()
+        })
+    }
+
+    /// Describes a `security.txt` that has expired or expires soon
+    pub fn txt_expiry_warning(root: &Path) -> Option<String> {
+        let expires = Self::txt_expiry(root)?;
+        let days = (expires.with_timezone(&Utc) - Utc::now()).num_days();
+        if expires.with_timezone(&Utc) < Utc::now() {
+            Some(format!(
+                "security.txt expired on {}. Run `cu security txt` to renew it.",
+                expires.format("%Y-%m-%d")
+            ))
+        } else if days < EXPIRY_WARNING_DAYS {
+            Some(format!(
+                "security.txt expires in {} day{} ({}). Run `cu security txt` to renew it.",
+                days,
+                if days == 1 { "" } else { "s" },
+                expires.format("%Y-%m-%d")
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_table_supports_the_newest_minor_lines() {
+        let versions = [(2, 1, 3), (2, 1, 0), (2, 0, 5), (1, 4, 2)];
+        assert_eq!(
+            SecurityManager::lines_table(&versions, 2),
+            "| Version | Supported          |
+| ------- | ------------------ |
+| 2.1.x   | :white_check_mark: |
+| 2.0.x   | :white_check_mark: |
+| 1.4.x   | :x:                |
+"
+        );
+    }
+
+    #[test]
+    fn lines_table_collapses_old_lines() {
+        let versions: Vec<(u64, u64, u64)> = (0..8).rev().map(|minor| (1, minor, 0)).collect();
+        let table = SecurityManager::lines_table(&versions, 1);
+        assert_eq!(table.matches(":white_check_mark:").count(), 1);
+        assert!(table.contains("| 1.3.x   | :x:                |\n"));
+        assert!(!table.contains("1.2.x"));
+        assert!(table.ends_with("| < 1.3   | :x:                |\n"));
+    }
+
+    #[test]
+    fn lines_table_without_releases_points_at_the_default_branch() {
+        assert!(
+            SecurityManager::lines_table(&[], 1).starts_with("No releases have been tagged yet.")
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::security::SecurityManager;
+
+/// Extensions accepted for community files, besides none at all
+const DOC_EXTENSIONS: &[&str] = &["md", "markdown", "txt", "rst", "adoc", "asciidoc", "org"];
+
+/// A community health file that forges recognize
+struct Standard {
+    id: &'static str,
+    title: &'static str,
+    weight: u32,
+    explanation: &'static str,
+    /// File stems matched case-insensitively, with or without a documentation extension
+    stems: &'static [&'static str],
+    /// Directories searched for the file, relative to the project root
+    locations: &'static [&'static str],
+    /// Template directories that satisfy the check when they contain files
+    directories: &'static [&'static str],
+}
+
+const STANDARDS: &[Standard] = &[
+    Standard {
+        id: "readme",
+        title: "README",
+        weight: 3,
+        explanation: "Tells people what the project does and how to use it. GitHub and GitLab show it on the project page.",
+        stems: &["readme"],
+        locations: &["", "docs", ".github"],
+        directories: &[],
+    },
+    Standard {
+        id: "license",
+        title: "LICENSE",
+        weight: 3,
+        explanation: "Without a license nobody may legally use, change or share the code. Forges only detect it in the project root.",
+        stems: &["license", "licence", "copying", "unlicense"],
+        locations: &[""],
+        directories: &[],
+    },
+    Standard {
+        id: "code-of-conduct",
+        title: "CODE_OF_CONDUCT",
+        weight: 2,
+        explanation: "Sets expectations for behavior and explains how to report problems.",
+        stems: &["code_of_conduct", "code-of-conduct"],
+        locations: &["", "docs", ".github"],
+        directories: &[],
+    },
+    Standard {
+        id: "contributing",
+        title: "CONTRIBUTING",
+        weight: 2,
+        explanation: "Explains how to build, test and submit changes. Linked when people open issues and pull requests.",
+        stems: &["contributing"],
+        locations: &["", "docs", ".github"],
+        directories: &[],
+    },
+    Standard {
+        id: "security",
+        title: "SECURITY",
+        weight: 2,
+        explanation: "Tells people how to report vulnerabilities privately instead of in public issues.",
+        stems: &["security"],
+        locations: &["", "docs", ".github"],
+        directories: &[],
+    },
+    Standard {
+        id: "support",
+        title: "SUPPORT",
+        weight: 1,
+        explanation: "Points people with questions to the right place instead of the issue tracker.",
+        stems: &["support"],
+        locations: &["", "docs", ".github"],
+        directories: &[],
+    },
+    Standard {
+        id: "issue-templates",
+        title: "Issue templates",
+        weight: 1,
+        explanation: "Guides reporters to include the information needed to act on bugs and feature requests.",
+        stems: &["issue_template"],
+        locations: &["", "docs", ".github"],
+        directories: &[".github/ISSUE_TEMPLATE", ".gitlab/issue_templates"],
+    },
+    Standard {
+        id: "pull-request-template",
+        title: "Pull request template",
+        weight: 1,
+        explanation: "Reminds contributors what a change needs before it can be reviewed.",
+        stems: &["pull_request_template"],
+        locations: &["", "docs", ".github"],
+        directories: &[
+            ".github/PULL_REQUEST_TEMPLATE",
+            ".gitlab/merge_request_templates",
+        ],
+    },
+    Standard {
+        id: "codeowners",
+        title: "CODEOWNERS",
+        weight: 1,
+        explanation: "Requests reviews from the people responsible for the changed code automatically.",
+        stems: &["codeowners"],
+        locations: &["", "docs", ".github", ".gitlab"],
+        directories: &[],
+    },
+];
+
+/// The result of one community standards check
+#[derive(Clone, Debug, Serialize)]
+pub struct StandardCheck {
+    pub id: String,
+    pub title: String,
+    pub passed: bool,
+    /// Where the file was found, relative to the project root
+    pub path: Option<String>,
+    pub weight: u32,
+    pub explanation: String,
+    /// Places that were searched
+    pub locations: Vec<String>,
+    /// Optional checks are reported but left out of the score
+    pub required: bool,
+    /// Files that break the check, such as sources without a license header
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+}
+
+/// The community standards of a project
+#[derive(Clone, Debug, Serialize)]
+pub struct StandardsReport {
+    /// Name of the policy the project was checked against, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+    pub checks: Vec<StandardCheck>,
+    pub score: u32,
+    pub max_score: u32,
+    pub percent: u32,
+    /// Problems with files that exist, such as an expired `security.txt`
+    pub warnings: Vec<String>,
+}
+
+impl StandardsReport {
+    /// Checks a project directory against the community standards
+    pub fn evaluate(root: &Path) -> Self {
+        let checks: Vec<StandardCheck> = STANDARDS
+            .iter()
+            .map(|standard| Self::check(root, standard))
+            .collect();
+        let mut report = Self::from_checks(checks);
+        report
+            .warnings
+            .extend(SecurityManager::txt_expiry_warning(root));
+        report
+    }
+
+    /// Checks for one community file by its id, such as `security`
+    pub fn check_standard(root: &Path, id: &str) -> Option<StandardCheck> {
+        STANDARDS
+            .iter()
+            .find(|standard| standard.id == id)
+            .map(|standard| Self::check(root, standard))
+    }
+
+    /// Returns the ids of the community files curator knows
+    pub fn standard_ids() -> Vec<&'static str> {
+        STANDARDS.iter().map(|standard| standard.id).collect()
+    }
+
+    fn check(root: &Path, standard: &Standard) -> StandardCheck {
+        let path = Self::find(root, standard);
+        let mut locations: Vec<String> = standard
+            .locations
+            .iter()
+            .map(|location| {
+                let name = standard.stems[0].to_uppercase();
+                if location.is_empty() {
+                    name
+                } else {
+                    format!("{location}/{name}")
+                }
+            })
+            .collect();
+        locations.extend(standard.directories.iter().map(|dir| format!("{dir}/")));
+        StandardCheck {
+            id: standard.id.to_string(),
+            title: standard.title.to_string(),
+            passed: path.is_some(),
+            path,
+            weight: standard.weight,
+            explanation: standard.explanation.to_string(),
+            locations,
+            required: true,
+            files: Vec::new(),
+        }
+    }
+
+    /// Builds a report and its score from finished checks, counting only required ones
+    pub fn from_checks(checks: Vec<StandardCheck>) -> Self {
+        let max_score: u32 = checks
+            .iter()
+            .filter(|check| check.required)
+            .map(|check| check.weight)
+            .sum();
+        let score: u32 = checks
+            .iter()
+            .filter(|check| check.required && check.passed)
+            .map(|check| check.weight)
+            .sum();
+        let percent = (score * 100).checked_div(max_score).unwrap_or(100);
+        StandardsReport {
+            policy: None,
+            checks,
+            score,
+            max_score,
+            percent,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Returns the checks that did not pass
+    pub fn missing(&self) -> impl Iterator<Item = &StandardCheck> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+
+    /// Finds the file or template directory satisfying a standard
+    fn find(root: &Path, standard: &Standard) -> Option<String> {
+        for location in standard.locations {
+            let dir = root.join(location);
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut names: Vec<String> = entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_file())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            let found = names.into_iter().find(|name| {
+                let lower = name.to_lowercase();
+                let (stem, extension) = match lower.rsplit_once('.') {
+                    Some((stem, extension)) => (stem, extension),
+                    None => (lower.as_str(), ""),
+                };
+                standard.stems.contains(&stem)
+                    && (extension.is_empty() || DOC_EXTENSIONS.contains(&extension))
+            });
+            if let Some(name) = found {
+                return Some(if location.is_empty() {
+                    name
+                } else {
+                    format!("{location}/{name}")
+                });
+            }
+        }
+        standard
+            .directories
+            .iter()
+            .find(|dir| {
+                fs::read_dir(root.join(dir))
+                    .map(|mut entries| entries.any(|entry| entry.is_ok_and(|e| e.path().is_file())))
+                    .unwrap_or(false)
+            })
+            .map(|dir| format!("{dir}/"))
+    }
+}
@@ -0,0 +1,103 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Where a stored template comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StoreScope {
+    User,
+    Org,
+}
+
+impl StoreScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StoreScope::User => "user",
+            StoreScope::Org => "org",
+        }
+    }
+}
+
+pub struct TemplateStore;
+
+impl TemplateStore {
+    /// Returns the user-level template directory
+    ///
+    /// Uses `CURATOR_HOME` when set, otherwise the platform configuration directory.
+    pub fn user_dir() -> Option<PathBuf> {
+        if let Some(dir) = env::var_os("CURATOR_HOME") {
+            return Some(PathBuf::from(dir).join("templates"));
+        }
+        let base = if cfg!(windows) {
+            env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+        base.map(|dir| dir.join("curator").join("templates"))
+    }
+
+    /// Returns the organization-level template directory from `CURATOR_ORG_DIR`
+    pub fn org_dir() -> Option<PathBuf> {
+        env::var_os("CURATOR_ORG_DIR")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Returns the directory holding templates of `kind` for the given scope
+    pub fn dir(kind: &str, scope: StoreScope) -> Option<PathBuf> {
+        match scope {
+            StoreScope::User => Self::user_dir(),
+            StoreScope::Org => Self::org_dir(),
+        }
+        .map(|dir| dir.join(kind))
+    }
+
+    /// Finds a stored template by file name, preferring the user store
+    pub fn find(kind: &str, file_name: &str) -> Option<(PathBuf, StoreScope)> {
+        [StoreScope::User, StoreScope::Org]
+            .into_iter()
+            .filter_map(|scope| Self::dir(kind, scope).map(|dir| (dir.join(file_name), scope)))
+            .find(|(path, _)| path.is_file())
+    }
+
+    /// Lists the file names of all stored templates of `kind`
+    pub fn list(kind: &str) -> Vec<(String, StoreScope)> {
+        let mut entries: Vec<(String, StoreScope)> = Vec::new();
+        for scope in [StoreScope::User, StoreScope::Org] {
+            let Some(dir) = Self::dir(kind, scope) else {
+                continue;
+            };
+            let Ok(read_dir) = fs::read_dir(dir) else {
+                continue;
+            };
+            let mut names: Vec<String> = read_dir
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_file())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| !entries.iter().any(|(existing, _)| existing == name))
+                .collect();
+            names.sort();
+            entries.extend(names.into_iter().map(|name| (name, scope)));
+        }
+        entries
+    }
+
+    /// Copies `content` into the store under `file_name`
+    pub fn add(
+        kind: &str,
+        file_name: &str,
+        content: &str,
+        scope: StoreScope,
+    ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let dir = Self::dir(kind, scope).ok_or_else(|| match scope {
+            StoreScope::User => "Could not determine the user template directory".to_string(),
+            StoreScope::Org => "CURATOR_ORG_DIR is not set".to_string(),
+        })?;
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(file_name);
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,17 @@
+use crossterm::style::Stylize;
+
+use crate::config::ConfigManager;
+use crate::tools::Tools;
+
+pub struct SupportManager;
+
+impl SupportManager {
+    /// Generates `SUPPORT.md`, prompting for missing values when `interactive`
+    pub fn generate(interactive: bool) -> Result<(), String> {
+        ConfigManager::load_config();
+        let template = include_str!("../templates/community/support.md");
+        Tools::write_template("SUPPORT.md", template, interactive)?;
+        println!("{} Created SUPPORT.md", "[SUCCESS]".green());
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+use crossterm::style::Stylize;
+use dialoguer::Input;
+use std::path::Path;
+use std::process;
+
+use crate::config::{ConfigManager, CONFIGURATION};
+use crate::git::Git;
+use crate::standards::StandardsReport;
+use crate::tools::Tools;
+
+const ISSUE_TEMPLATE_DIR: &str = ".github/ISSUE_TEMPLATE";
+const PULL_REQUEST_TEMPLATE: &str = ".github/pull_request_template.md";
+
+/// Issue templates as `(name, markdown, issue form)`
+const ISSUE_TEMPLATES: &[(&str, &str, &str)] = &[
+    (
+        "bug_report",
+        include_str!("../templates/github/bug_report.md"),
+        include_str!("../templates/github/bug_report.yml"),
+    ),
+    (
+        "feature_request",
+        include_str!("../templates/github/feature_request.md"),
+        include_str!("../templates/github/feature_request.yml"),
+    ),
+    (
+        "question",
+        include_str!("../templates/github/question.md"),
+        include_str!("../templates/github/question.yml"),
+    ),
+];
+
+/// Community files the templates link to
+struct Links {
+    conduct: Option<(String, Option<String>)>,
+    security: Option<(String, Option<String>)>,
+    contributing: Option<(String, Option<String>)>,
+    support: Option<(String, Option<String>)>,
+}
+
+pub struct TemplatesManager;
+
+impl TemplatesManager {
+    /// Writes the GitHub issue templates, their `config.yml` and the pull request template
+    ///
+    /// Uses issue forms (YAML) when `forms`. Existing files are kept unless `force`.
+    pub fn github(forms: bool, force: bool) {
+        ConfigManager::load_config();
+        let links = Self::links();
+        let written = Self::write_issue_templates(&links, forms, force)
+            .and_then(|()| Self::write_pull_request_template(&links, force));
+        if let Err(e) = written {
+            eprintln!("{} {}", "[ERROR]".red(), e);
+            process::exit(1);
+        }
+    }
+
+    /// Generates the issue templates for `cu standards fix`
+    pub fn generate_issue_templates(interactive: bool) -> Result<(), String> {
+        let forms = interactive
+            && Input::<String>::new()
+                .with_prompt("Use issue forms (YAML) instead of Markdown templates? (y/n)")
+                .default("y".into())
+                .interact_text()
+                .unwrap()
+                .to_lowercase()
+                .starts_with('y');
+        Self::write_issue_templates(&Self::links(), forms, false)
+    }
+
+    /// Generates the pull request template for `cu standards fix`
+    pub fn generate_pull_request_template(_interactive: bool) -> Result<(), String> {
+        Self::write_pull_request_template(&Self::links(), false)
+    }
+
+    fn write_issue_templates(links: &Links, forms: bool, force: bool) -> Result<(), String> {
+        let project = Self::project();
+        for (name, markdown, form) in ISSUE_TEMPLATES {
+            let is_bug = *name == "bug_report";
+            let text = if forms {
+                form.replace("{{notice_yaml}}\n", &Self::notice_yaml(links, is_bug))
+                    .replace("{{conduct_yaml}}\n", &Self::conduct_yaml(links))
+            } else {
+                markdown.replace("{{notice}}\n", &Self::notice(links, is_bug))
+            };
+            let text = text.replace("{{project}}", &Self::yaml_escape(&project));
+            let extension = if forms { "yml" } else { "md" };
+            let path = format!("{ISSUE_TEMPLATE_DIR}/{name}.{extension}");
+            // GitHub lists both formats in the template chooser, so only one may remain
+            let others: Vec<String> = ["md", "yml", "yaml"]
+                .iter()
+                .filter(|other| **other != extension)
+                .map(|other| format!("{ISSUE_TEMPLATE_DIR}/{name}.{other}"))
+                .filter(|other| Path::new(other).exists())
+                .collect();
+            if !force {
+                if let Some(other) = others.first() {
+                    Self::skip(other);
+                    continue;
+                }
+            }
+            if Self::write(&path, &text, force)? {
+                for other in others {
+                    std::fs::remove_file(&other)
+                        .map_err(|e| format!("Failed to remove {other}: {e}"))?;
+                    println!("{} Removed {}", "[SUCCESS]".green(), other);
+                }
+            }
+        }
+        Self::write(
+            &format!("{ISSUE_TEMPLATE_DIR}/config.yml"),
+            &Self::issue_config(links),
+            force,
+        )
+        .map(|_| ())
+    }
+
+    fn write_pull_request_template(links: &Links, force: bool) -> Result<(), String> {
+        let contributing = match &links.contributing {
+            Some(file) => Self::link("contributing guidelines", file),
+            None => "contributing guidelines".to_string(),
+        };
+        let conduct = match &links.conduct {
+            Some(file) => format!(
+                "- [ ] I agree to follow the {}\n",
+                Self::link("Code of Conduct", file)
+            ),
+            None => String::new(),
+        };
+        let text = include_str!("../templates/github/pull_request_template.md")
+            .replace("{{contributing}}", &contributing)
+            .replace("{{conduct_item}}\n", &conduct);
+        Self::write(PULL_REQUEST_TEMPLATE, &text, force).map(|_| ())
+    }
+
+    /// Writes `text` to `path` unless it exists and `force` is not set, returning whether it wrote
+    fn write(path: &str, text: &str, force: bool) -> Result<bool, String> {
+        if Path::new(path).exists() && !force {
+            Self::skip(path);
+            return Ok(false);
+        }
+        Tools::write_file(path, text)?;
+        println!("{} Created {}", "[SUCCESS]".green(), path);
+        Ok(true)
+    }
+
+    fn skip(path: &str) {
+        println!(
+            "{} {} already exists, skipping. Use `{}` to overwrite it.",
+            "[INFO]".yellow(),
+            path,
+            "cu templates github --force".grey()
+        );
+    }
+
+    /// Finds the code of conduct, security policy, contributing guide and support file
+    fn links() -> Links {
+        let report = StandardsReport::evaluate(Path::new("."));
+        let find = |id: &str| {
+            report
+                .checks
+                .iter()
+                .find(|check| check.id == id)
+                .and_then(|check| check.path.clone())
+                .map(|path| {
+                    let url = Git::file_url(&path);
+                    (path, url)
+                })
+        };
+        Links {
+            conduct: find("code-of-conduct"),
+            security: find("security"),
+            contributing: find("contributing"),
+            support: find("support"),
+        }
+    }
+
+    fn project() -> String {
+        let config_guard = CONFIGURATION.lock().unwrap();
+        config_guard
+            .get("settings")
+            .and_then(|settings| settings.get("project"))
+            .cloned()
+            .unwrap_or_else(|| "this project".to_string())
+    }
+
+    /// Links to the file on the forge, or names its path when there is no remote
+    fn link(title: &str, (path, url): &(String, Option<String>)) -> String {
+        match url {
+            Some(url) => format!("[{title}]({url})"),
+            None => format!("{title} (`{path}`)"),
+        }
+    }
+
+    /// Lines shown at the top of an issue about conduct and, for bugs, private vulnerability reports
+    fn notice_lines(links: &Links, security: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let (true, Some(file)) = (security, &links.security) {
+            lines.push(format!(
+                "Please do not report security vulnerabilities in public issues. Follow the {} instead.",
+                Self::link("security policy", file)
+            ));
+        }
+        if let Some(file) = &links.conduct {
+            lines.push(format!(
+                "By opening an issue you agree to follow the {}.",
+                Self::link("Code of Conduct", file)
+            ));
+        }
+        lines
+    }
+
+    fn notice(links: &Links, security: bool) -> String {
+        let lines = Self::notice_lines(links, security);
+        if lines.is_empty() {
+            return String::new();
+        }
+        let quoted: Vec<String> = lines.iter().map(|line| format!("> {line}")).collect();
+        format!("{}\n\n", quoted.join("\n>\n"))
+    }
+
+    fn notice_yaml(links: &Links, security: bool) -> String {
+        Self::notice_lines(links, security)
+            .iter()
+            .map(|line| format!("\n        {line}\n"))
+            .collect()
+    }
+
+    fn conduct_yaml(links: &Links) -> String {
+        let Some(file) = &links.conduct else {
+            return String::new();
+        };
+        format!(
+            "  - type: checkboxes\n    id: terms\n    attributes:\n      label: Code of Conduct\n      description: \"{}\"\n      options:\n        - label: I agree to follow this project's Code of Conduct\n          required: true\n",
+            Self::yaml_escape(&format!(
+                "By submitting this issue, you agree to follow our {}.",
+                Self::link("Code of Conduct", file)
+            ))
+        )
+    }
+
+    /// Builds `config.yml` with contact links to the security policy and support file
+    ///
+    /// Blank issues stay disabled unless `data.blank-issues-enabled` is `true`.
+    fn issue_config(links: &Links) -> String {
+        let mut contact_links = String::new();
+        let entries = [
+            (
+                &links.security,
+                "Report a security vulnerability",
+                "Please report vulnerabilities privately as described in the security policy.",
+            ),
+            (
+                &links.support,
+                "Get help",
+                "Questions and support requests are answered here.",
+            ),
+        ];
+        for (file, name, about) in entries {
+            if let Some((_, Some(url))) = file {
+                contact_links.push_str(&format!(
+                    "  - name: {name}\n    url: {url}\n    about: {about}\n"
+                ));
+            }
+        }
+        let blank_issues = {
+            let config_guard = CONFIGURATION.lock().unwrap();
+            config_guard
+                .get("data")
+                .and_then(|data| data.get("blank-issues-enabled"))
+                .is_some_and(|enabled| enabled == "true")
+        };
+        if contact_links.is_empty() {
+            format!("blank_issues_enabled: {blank_issues}\ncontact_links: []\n")
+        } else {
+            format!("blank_issues_enabled: {blank_issues}\ncontact_links:\n{contact_links}")
+        }
+    }
+
+    /// Escapes a string for a double-quoted YAML scalar
+    fn yaml_escape(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+}
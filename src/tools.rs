@@ -1,6 +1,8 @@
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 
+use crate::license::LicenseManager;
+
 pub struct Tools;
 
 impl Tools {
@@ -17,11 +19,166 @@ impl Tools {
         if matches.len() > 3 {
             matches.select_nth_unstable_by(2, |a, b| b.1.cmp(&a.1));
             matches.truncate(3);
-            matches.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+            matches.sort_unstable_by_key(|m| std::cmp::Reverse(m.1));
         } else {
-            matches.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+            matches.sort_unstable_by_key(|m| std::cmp::Reverse(m.1));
         }
 
         matches
     }
+
+    /// Lists the project files, using git to skip ignored ones when possible
+    ///
+    /// Outside a repository the tree is walked, skipping hidden directories and build output.
+    pub fn project_files(root: &std::path::Path) -> Vec<std::path::PathBuf> {
+        if root == std::path::Path::new(".") {
+            if let Some(files) = crate::git::Git::files() {
+                return files
+                    .into_iter()
+                    .map(std::path::PathBuf::from)
+                    .filter(|file| file.is_file())
+                    .collect();
+            }
+        }
+        let mut files = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                if path.is_dir() {
+                    if !name.starts_with('.') && name != "target" && name != "node_modules" {
+                        dirs.push(path);
+                    }
+                } else if path.is_file() {
+                    files.push(path.strip_prefix(root).unwrap_or(&path).to_path_buf());
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    /// Matches a path against a glob where `*` and `?` stay within one path component
+    /// and `**` spans directories
+    pub fn glob_match(pattern: &str, path: &str) -> bool {
+        fn matches(pattern: &[char], path: &[char]) -> bool {
+            match pattern {
+                [] => path.is_empty(),
+                ['*', '*', '/', rest @ ..] => {
+                    matches(rest, path)
+                        || (0..path.len())
+                            .filter(|&i| path[i] == '/')
+                            .any(|i| matches(rest, &path[i + 1..]))
+                }
+                ['*', '*', rest @ ..] => (0..=path.len()).any(|i| matches(rest, &path[i..])),
+                ['*', rest @ ..] => (0..=path.len())
+                    .take_while(|&i| i == 0 || path[i - 1] != '/')
+                    .any(|i| matches(rest, &path[i..])),
+                ['?', rest @ ..] => {
+                    path.first().is_some_and(|&c| c != '/') && matches(rest, &path[1..])
+                }
+                [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+            }
+        }
+        let pattern: Vec<char> = pattern.chars().collect();
+        let path: Vec<char> = path.chars().collect();
+        matches(&pattern, &path)
+    }
+
+    /// Compares dotted version strings such as `1.4` and `2.1` numerically
+    pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+        let parse = |version: &str| -> Vec<u64> {
+            version
+                .trim_start_matches('v')
+                .split(['.', '-', '+'])
+                .map_while(|part| part.parse().ok())
+                .collect()
+        };
+        parse(a).cmp(&parse(b))
+    }
+
+    /// Scores how alike two texts are, from 0.0 to 1.0, ignoring case, punctuation and wrapping
+    pub fn text_similarity(a: &str, b: &str) -> f64 {
+        let shingles = |text: &str| -> std::collections::HashSet<String> {
+            let mut words: Vec<String> = Vec::new();
+            let mut word = String::new();
+            for c in text.chars().flat_map(char::to_lowercase) {
+                if c.is_alphanumeric() && c < '\u{3000}' {
+                    word.push(c);
+                    continue;
+                }
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                if c.is_alphanumeric() {
+                    // Scripts without spaces between words are compared character by character
+                    words.push(c.to_string());
+                }
+            }
+            if !word.is_empty() {
+                words.push(word);
+            }
+            words.windows(3).map(|w| w.join(" ")).collect()
+        };
+        let a = shingles(a);
+        let b = shingles(b);
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        let shared = a.intersection(&b).count();
+        shared as f64 / (a.len() + b.len() - shared) as f64
+    }
+
+    /// Fills the placeholders of a template
+    ///
+    /// Prompts for missing values when `interactive`, otherwise only uses `curator.json`.
+    pub fn fill_template(template: &str, interactive: bool) -> Result<String, String> {
+        if interactive {
+            Ok(LicenseManager::modify_license(template.to_string()))
+        } else {
+            LicenseManager::modify_license_stored(template.to_string())
+        }
+    }
+
+    /// Fills the placeholders of a template and writes it to `filename`
+    pub fn write_template(filename: &str, template: &str, interactive: bool) -> Result<(), String> {
+        let text = Self::fill_template(template, interactive)?;
+        Self::write_file(filename, &text)
+    }
+
+    /// Writes `text` to `filename`, creating parent directories as needed
+    pub fn write_file(filename: &str, text: &str) -> Result<(), String> {
+        if let Some(parent) = std::path::Path::new(filename).parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+            }
+        }
+        std::fs::write(filename, text).map_err(|e| format!("Failed to write {filename}: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_star_stays_within_a_component() {
+        assert!(Tools::glob_match("*.rs", "lib.rs"));
+        assert!(!Tools::glob_match("*.rs", "src/lib.rs"));
+        assert!(Tools::glob_match("src/?ib.rs", "src/lib.rs"));
+        assert!(!Tools::glob_match("src/?ib.rs", "src/glib.rs"));
+    }
+
+    #[test]
+    fn glob_double_star_spans_directories() {
+        assert!(Tools::glob_match("src/**/mod.rs", "src/mod.rs"));
+        assert!(Tools::glob_match("src/**/mod.rs", "src/a/b/mod.rs"));
+        assert!(Tools::glob_match("docs/**", "docs/a/b.md"));
+        assert!(!Tools::glob_match("src/**/mod.rs", "tests/mod.rs"));
+    }
 }